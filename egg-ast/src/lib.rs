@@ -1,3 +1,5 @@
 mod ln_col;
+mod span;
 
 pub use ln_col::*;
+pub use span::*;
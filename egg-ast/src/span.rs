@@ -0,0 +1,134 @@
+use crate::{LnCol, LnNum};
+use std::fmt::{self, Debug, Formatter};
+
+/// Location of a character in a source text.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// Number of bytes from the start of the source text.
+    pub offset: usize,
+    /// Line number and column number of the character.
+    pub ln_col: LnCol,
+}
+
+impl Position {
+    /// Create a position.
+    pub const fn new(offset: usize, ln_col: LnCol) -> Self {
+        Position { offset, ln_col }
+    }
+
+    /// Position of the first character of a source text.
+    pub const fn start_of_text() -> Self {
+        Position::new(0, LnCol::from_pred_counts(0, 0))
+    }
+
+    /// Advance over a piece of text that doesn't contain any EOL character.
+    ///
+    /// The column number is advanced by the number of characters (Unicode scalar values) in `text`.
+    pub fn advance_inline(mut self, text: &str) -> Self {
+        self.offset += text.len();
+        self.ln_col = self.ln_col.advance_column(text.chars().count());
+        self
+    }
+
+    /// Move the position by the same distance that it would take to move `from` to `to`.
+    ///
+    /// Column numbers are unchanged, so `from` and `to` are expected to be the starts of lines,
+    /// and `self` is expected to not precede `from`.
+    pub fn rebase(self, from: Position, to: Position) -> Self {
+        let offset = self.offset - from.offset + to.offset;
+        let ln_pred = self.ln_col.line.pred_count() - from.ln_col.line.pred_count()
+            + to.ln_col.line.pred_count();
+        let ln_col = LnCol::new(LnNum::from_pred_count(ln_pred), self.ln_col.column);
+        Position::new(offset, ln_col)
+    }
+}
+
+impl Debug for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Position { offset, ln_col } = self;
+        write!(f, "Position {offset} ({ln_col})")
+    }
+}
+
+/// Location of a piece of text in a source text.
+///
+/// The start is inclusive, the end is exclusive.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Position of the first character.
+    pub start: Position,
+    /// Position right after the last character.
+    pub end: Position,
+}
+
+impl Span {
+    /// Create a span.
+    pub const fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+
+    /// Create a span of a piece of text that doesn't contain any EOL character.
+    pub fn inline(start: Position, text: &str) -> Self {
+        Span::new(start, start.advance_inline(text))
+    }
+
+    /// Move the span by the same distance that it would take to move `from` to `to`.
+    ///
+    /// See [`Position::rebase`].
+    pub fn rebase(self, from: Position, to: Position) -> Self {
+        Span::new(self.start.rebase(from, to), self.end.rebase(from, to))
+    }
+
+    /// Number of bytes covered by the span.
+    pub const fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    /// Check if the span covers no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Debug for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Span { start, end } = self;
+        write!(
+            f,
+            "Span {}..{} ({}-{})",
+            start.offset, end.offset, start.ln_col, end.ln_col,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn advance_inline() {
+        let start = Position::new(4, LnCol::from_pred_counts(2, 1));
+        let received = start.advance_inline("I ❤️ U");
+        let expected = Position::new(14, LnCol::from_pred_counts(2, 7));
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn rebase() {
+        let from = Position::new(10, LnCol::from_pred_counts(2, 0));
+        let to = Position::new(4, LnCol::from_pred_counts(1, 0));
+        let received = Position::new(15, LnCol::from_pred_counts(3, 2)).rebase(from, to);
+        let expected = Position::new(9, LnCol::from_pred_counts(2, 2));
+        assert_eq!(received, expected);
+        assert_eq!(
+            expected.rebase(to, from),
+            Position::new(15, LnCol::from_pred_counts(3, 2))
+        );
+    }
+
+    #[test]
+    fn debug_fmt() {
+        let span = Span::inline(Position::start_of_text(), "hello");
+        assert_eq!(format!("{span:?}"), "Span 0..5 (1:1-1:6)");
+    }
+}
@@ -144,3 +144,48 @@ where
         (word, rest)
     }
 }
+
+/// Extract a sequence of string whose first char, last char, and middle chars
+/// have 3 different requirements.
+///
+/// Unlike [`split_hbt_ascii`], the 3 character verifiers will run on all characters.
+///
+/// **Return:**
+/// * The first item of the tuple is the resulting sequence of string.
+/// * The second item of the tuple is the remaining part of the input string.
+pub fn split_hbt<VerifyHead, VerifyBody, VerifyTail>(
+    input: &str,
+    is_head: VerifyHead,
+    is_body: VerifyBody,
+    is_tail: VerifyTail,
+) -> (&'_ str, &'_ str)
+where
+    VerifyHead: Fn(&char) -> bool,
+    VerifyBody: Fn(&char) -> bool,
+    VerifyTail: Fn(&char) -> bool,
+{
+    let mut iter = input.chars();
+
+    let Some(first_char) = iter.next() else {
+        return ("", input);
+    };
+    if !is_head(&first_char) {
+        return ("", input);
+    }
+
+    let tail_size: usize = iter.take_while(is_body).map(char::len_utf8).sum();
+    let end_offset = first_char.len_utf8() + tail_size;
+
+    let word = &input[..end_offset];
+    let last_char = word.chars().next_back().expect("word is not empty");
+
+    if is_tail(&last_char) {
+        let rest = &input[end_offset..];
+        (word, rest)
+    } else {
+        let end_offset = end_offset - last_char.len_utf8();
+        let word = &input[..end_offset];
+        let rest = &input[end_offset..];
+        (word, rest)
+    }
+}
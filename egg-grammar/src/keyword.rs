@@ -1,7 +1,11 @@
-use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+use strum::{
+    AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, FromRepr, IntoStaticStr,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)] // essential std traits
-#[derive(AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr)] // essential strum traits
+#[derive(
+    AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, FromRepr, IntoStaticStr,
+)] // essential strum traits
 #[strum(use_phf, serialize_all = "lowercase")]
 pub enum Keyword {
     /* Visibility keywords */
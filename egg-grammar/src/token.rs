@@ -1,17 +1,36 @@
+mod analyze_indent;
+mod attribute;
 mod ending;
+mod eol;
 mod indent;
+mod indent_stack;
+mod intern;
 mod invalid;
+mod layout;
+mod map_content;
 mod middle;
 mod parse_token;
 mod raw;
 mod scan;
 mod token_line;
+mod token_tree;
+mod verify;
+mod write;
 
+pub use analyze_indent::*;
+pub use attribute::*;
 pub use ending::*;
+pub use eol::*;
 pub use indent::*;
+pub use intern::*;
 pub use invalid::*;
+pub use layout::*;
+pub use map_content::*;
 pub use middle::*;
 pub use parse_token::*;
 pub use raw::*;
 pub use scan::*;
 pub use token_line::*;
+pub use token_tree::*;
+pub use verify::*;
+pub use write::*;
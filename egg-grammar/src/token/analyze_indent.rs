@@ -0,0 +1,233 @@
+use super::{
+    indent_stack::{is_blank, IndentStack},
+    IndentChar, IndentToken, TokenLine,
+};
+use crate::ast::Span;
+use derive_more::{Display, Error};
+
+/// Number of columns of an indentation level when it can't be inferred from the text.
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
+/// Kind of [`IndentDiagnostic`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum IndentDiagnosticKind {
+    /// The indentation has both spaces and tabs.
+    #[display(fmt = "Indentation mixes spaces and tabs")]
+    MixedChars,
+    /// The indentation is neither a start of the indentation of the enclosing block
+    /// nor does it start with it.
+    #[display(fmt = "Indentation is incompatible with the enclosing block")]
+    IncompatiblePrefix,
+    /// The indentation only uses the character that is not the [dominant style](IndentAnalysis::style).
+    #[display(fmt = "Indentation doesn't follow the dominant style")]
+    NotDominant,
+}
+
+/// Problem with the indentation of a line.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+#[display(fmt = "{}: {}", "span.start.ln_col", kind)]
+pub struct IndentDiagnostic {
+    /// Kind of the problem.
+    pub kind: IndentDiagnosticKind,
+    /// Location of the indentation of the line.
+    pub span: Span,
+    /// Indentation of the same width in the dominant style.
+    #[error(not(source))]
+    pub suggestion: IndentToken,
+}
+
+/// Result of [`analyze_indent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndentAnalysis {
+    /// Character used by most indented lines.
+    ///
+    /// `None` if no line is indented by only one kind of character.
+    pub style: Option<IndentChar>,
+    /// Number of columns of an indentation level, which is also the width of a tab.
+    ///
+    /// It is the greatest common divisor of the indentations that only have spaces,
+    /// or [`DEFAULT_INDENT_WIDTH`] if there are no such indentations.
+    pub width: usize,
+    /// Problems with the indentation of the lines, in the order of the lines.
+    pub diagnostics: Vec<IndentDiagnostic>,
+}
+
+impl IndentAnalysis {
+    /// Create an indentation that is as wide as `indent` but in the dominant style.
+    ///
+    /// A tab advances to the next multiple of [`width`](IndentAnalysis::width).
+    /// Without a dominant style, spaces are used.
+    pub fn suggest(&self, indent: &[IndentChar]) -> IndentToken {
+        let columns = indent.iter().fold(0, |columns, char| match char {
+            IndentChar::Space => columns + 1,
+            IndentChar::Tab => (columns / self.width + 1) * self.width,
+        });
+        let chars = match self.style {
+            Some(IndentChar::Tab) => {
+                let tabs = std::iter::repeat(IndentChar::Tab).take(columns / self.width);
+                let spaces = std::iter::repeat(IndentChar::Space).take(columns % self.width);
+                tabs.chain(spaces).collect()
+            }
+            Some(IndentChar::Space) | None => vec![IndentChar::Space; columns],
+        };
+        IndentToken::from(chars)
+    }
+}
+
+/// Analyze the indentation of all lines of a text.
+///
+/// Lines without tokens are ignored. Lines with only a comment are not compared against
+/// the enclosing block, but they are still checked for mixed and non-dominant indentation.
+pub fn analyze_indent<Content>(lines: &[TokenLine<Content>]) -> IndentAnalysis {
+    let lines: Vec<_> = lines
+        .iter()
+        .filter(|line| !line.middle.is_empty() || line.ending.is_some())
+        .collect();
+    let is_uniform = |indent: &IndentToken, char: IndentChar| {
+        !indent.is_empty() && indent.iter().all(|item| *item == char)
+    };
+    let count = |char| {
+        lines
+            .iter()
+            .filter(|line| is_uniform(&line.indent.token, char))
+            .count()
+    };
+    let (spaces, tabs) = (count(IndentChar::Space), count(IndentChar::Tab));
+    let style = match (spaces, tabs) {
+        (0, 0) => None,
+        _ if tabs > spaces => Some(IndentChar::Tab),
+        _ => Some(IndentChar::Space),
+    };
+    let width = lines
+        .iter()
+        .filter(|line| is_uniform(&line.indent.token, IndentChar::Space))
+        .map(|line| line.indent.token.len())
+        .reduce(gcd)
+        .unwrap_or(DEFAULT_INDENT_WIDTH);
+    let mut analysis = IndentAnalysis {
+        style,
+        width,
+        diagnostics: Vec::new(),
+    };
+
+    let mut stack = IndentStack::default();
+    for line in lines {
+        let indent = &line.indent.token;
+        let mut kinds = Vec::new();
+
+        if !is_blank(line) && stack.push(indent).is_inconsistent() {
+            kinds.push(IndentDiagnosticKind::IncompatiblePrefix);
+        }
+
+        let has = |char| indent.contains(&char);
+        if has(IndentChar::Space) && has(IndentChar::Tab) {
+            kinds.push(IndentDiagnosticKind::MixedChars);
+        } else if style.map_or(false, |style| !indent.is_empty() && !has(style)) {
+            kinds.push(IndentDiagnosticKind::NotDominant);
+        }
+
+        for kind in kinds {
+            analysis.diagnostics.push(IndentDiagnostic {
+                kind,
+                span: line.indent.span,
+                suggestion: analysis.suggest(indent),
+            });
+        }
+    }
+
+    analysis
+}
+
+/// Greatest common divisor.
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::Scan;
+    use pretty_assertions::assert_eq;
+
+    /// Summarize the analysis as the style, the width, and the diagnostics with suggestions.
+    fn analyze(text: &str) -> (Option<IndentChar>, usize, Vec<String>) {
+        let lines: Vec<_> = Scan::new(text).collect();
+        let IndentAnalysis {
+            style,
+            width,
+            diagnostics,
+        } = analyze_indent(&lines);
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| format!("{diagnostic} -> {:?}", diagnostic.suggestion.to_string()))
+            .collect();
+        (style, width, diagnostics)
+    }
+
+    #[test]
+    fn consistent() {
+        let text = "if a then\n    if b then\n        print(b)\n\n    print(a)\n";
+        assert_eq!(analyze(text), (Some(IndentChar::Space), 4, vec![]));
+        let text = "if a then\n\tif b then\n\t\tprint(b)\n\tprint(a)\n";
+        assert_eq!(analyze(text), (Some(IndentChar::Tab), 4, vec![]));
+        assert_eq!(analyze("print(a)\nprint(b)\n"), (None, 4, vec![]));
+        let text = "if a then\n  if b then\n    print(b)\n";
+        assert_eq!(analyze(text), (Some(IndentChar::Space), 2, vec![]));
+    }
+
+    #[test]
+    fn whitespace_only_lines() {
+        let text = "if a then\n    b\n   \n    c\n";
+        assert_eq!(analyze(text), (Some(IndentChar::Space), 4, vec![]));
+        let text = "if a then\n\tb\n    \n \t\n\tc\n";
+        assert_eq!(analyze(text), (Some(IndentChar::Tab), 4, vec![]));
+    }
+
+    #[test]
+    fn mixed_chars() {
+        let text = "if a then\n    if b then\n    \tprint(b)\n";
+        let expected = vec![r#"3:1: Indentation mixes spaces and tabs -> "        ""#.to_string()];
+        assert_eq!(analyze(text), (Some(IndentChar::Space), 4, expected));
+    }
+
+    #[test]
+    fn incompatible_prefix() {
+        let text = "if a then\n    print(a)\n\tprint(b)\n    # comment\n    print(c)\n";
+        let expected = vec![
+            r#"3:1: Indentation is incompatible with the enclosing block -> "    ""#.to_string(),
+            r#"3:1: Indentation doesn't follow the dominant style -> "    ""#.to_string(),
+            r#"5:1: Indentation is incompatible with the enclosing block -> "    ""#.to_string(),
+        ];
+        assert_eq!(analyze(text), (Some(IndentChar::Space), 4, expected));
+    }
+
+    #[test]
+    fn tab_style() {
+        let text = "if a then\n\tif b then\n\t\tprint(b)\n    \tprint(c)\n";
+        let expected = vec![
+            r#"4:1: Indentation is incompatible with the enclosing block -> "\t\t""#.to_string(),
+            r#"4:1: Indentation mixes spaces and tabs -> "\t\t""#.to_string(),
+        ];
+        assert_eq!(analyze(text), (Some(IndentChar::Tab), 4, expected));
+    }
+
+    #[test]
+    fn display() {
+        let lines: Vec<_> = Scan::new("a\n \tb\n").collect();
+        let analysis = analyze_indent(&lines);
+        let received: Vec<_> = analysis
+            .diagnostics
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(received, ["2:1: Indentation mixes spaces and tabs"]);
+        assert_eq!(
+            analysis.suggest(&lines[1].indent.token),
+            IndentToken::from(vec![IndentChar::Space; 4]),
+        );
+    }
+}
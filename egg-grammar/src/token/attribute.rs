@@ -0,0 +1,219 @@
+use super::{
+    operator::Operator, EndingToken, InvalidToken, MiddleToken, TextToken, TokenLine,
+    TokenLineItem, WordToken,
+};
+use crate::ast::{Position, Span};
+use derive_more::{Display, Error};
+
+/// [`TokenLineItem`] of a [`MiddleToken`] or an [`InvalidToken`].
+type MiddleItem<Content> = TokenLineItem<Content, Result<MiddleToken<Content>, InvalidToken>>;
+
+/// Whether an [`Attribute`] annotates the item after it or the item that contains it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// Attribute of the item after it (`@path`).
+    Outer,
+    /// Attribute of the item that contains it (`@!path`).
+    Inner,
+}
+
+/// Attribute annotation at the start of a [`TokenLine`].
+///
+/// **Example:** In `@cli::help::params '''`, the path is `cli`, `help`, `params` and
+/// the argument is the multi-line string that starts with `'''`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute<'a, Content> {
+    /// Whether the attribute is outer (`@`) or inner (`@!`).
+    pub kind: AttributeKind,
+    /// Segments of the `::`-separated path after the marker.
+    pub path: Vec<&'a WordToken<Content>>,
+    /// Location of the marker and the path.
+    pub span: Span,
+    /// Tokens after the path on the same line, not including whitespaces.
+    pub arguments: Vec<&'a MiddleItem<Content>>,
+    /// Multi-line string at the end of the line.
+    pub text: Option<&'a TextToken<Content>>,
+}
+
+/// Error when grouping the tokens of an [`Attribute`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AttributeError {
+    #[display(fmt = "Attribute marker at {} is not followed by a path", "at.ln_col")]
+    MissingPath { at: Position },
+    #[display(
+        fmt = "Path separator at {} is not followed by a path segment",
+        "at.ln_col"
+    )]
+    IncompletePath { at: Position },
+}
+
+/// Get the word of an item.
+fn as_word<Content>(item: &MiddleItem<Content>) -> Option<&'_ WordToken<Content>> {
+    match &item.token {
+        Ok(MiddleToken::Word(word)) => Some(word),
+        _ => None,
+    }
+}
+
+/// Check if an item is a path separator (`::`).
+fn is_path_separator<Content>(item: &MiddleItem<Content>) -> bool
+where
+    Content: AsRef<str>,
+{
+    match &item.token {
+        Ok(MiddleToken::Operator(operator)) => operator.0.as_ref() == Operator::Path.as_str(),
+        _ => false,
+    }
+}
+
+impl<Content> TokenLine<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Group the tokens of the line into an [`Attribute`].
+    ///
+    /// Return `None` if the line doesn't start with an attribute marker (`@` or `@!`).
+    pub fn attribute(&self) -> Option<Result<Attribute<'_, Content>, AttributeError>> {
+        let mut items = self.middle.iter().peekable();
+        let marker = items.next()?;
+        let Ok(MiddleToken::Operator(operator)) = &marker.token else {
+            return None;
+        };
+        let (kind, marker_len) = match operator.split().ok()?.first()?.operator {
+            Operator::At => (AttributeKind::Outer, Operator::At.as_str().len()),
+            Operator::AtBang => (AttributeKind::Inner, Operator::AtBang.as_str().len()),
+            _ => return None,
+        };
+
+        if operator.0.as_ref().len() != marker_len {
+            let at = marker
+                .span
+                .start
+                .advance_inline(&marker.src_text.as_ref()[..marker_len]);
+            return Some(Err(AttributeError::MissingPath { at }));
+        }
+
+        let Some(first) = items.next_if(|item| as_word(item).is_some()) else {
+            let at = marker.span.end;
+            return Some(Err(AttributeError::MissingPath { at }));
+        };
+        let mut path = Vec::new();
+        path.extend(as_word(first));
+        let mut end = first.span.end;
+        while let Some(separator) = items.next_if(|item| is_path_separator(item)) {
+            let Some(segment) = items.next_if(|item| as_word(item).is_some()) else {
+                let at = separator.span.end;
+                return Some(Err(AttributeError::IncompletePath { at }));
+            };
+            path.extend(as_word(segment));
+            end = segment.span.end;
+        }
+
+        let arguments = items
+            .filter(|item| !matches!(item.token, Ok(MiddleToken::Whitespace(_))))
+            .collect();
+        let text = self.ending.as_ref().and_then(|ending| match &ending.token {
+            EndingToken::Text(text) => Some(text),
+            _ => None,
+        });
+
+        Some(Ok(Attribute {
+            kind,
+            path,
+            span: Span::new(marker.span.start, end),
+            arguments,
+            text,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::Scan;
+    use pretty_assertions::assert_eq;
+
+    /// Summary of an attribute that is easy to compare.
+    type Summary<'a> = (AttributeKind, Vec<String>, Vec<&'a str>, bool);
+
+    fn summarize<'a>(attribute: Attribute<'_, &'a str>) -> Summary<'a> {
+        let path = attribute
+            .path
+            .iter()
+            .map(|word| word.as_str().to_string())
+            .collect();
+        let arguments = attribute
+            .arguments
+            .iter()
+            .map(|item| item.src_text)
+            .collect();
+        (attribute.kind, path, arguments, attribute.text.is_some())
+    }
+
+    fn attribute(input: &str) -> Option<Result<Summary<'_>, AttributeError>> {
+        let line = Scan::new(input).next().expect("input should have a line");
+        line.attribute().map(|result| result.map(summarize))
+    }
+
+    #[test]
+    fn positive() {
+        use AttributeKind::*;
+
+        macro_rules! case {
+            ($input:expr => $kind:ident [$($path:literal),*] [$($argument:literal),*] $text:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let expected = ($kind, vec![$($path.to_string()),*], vec![$($argument),*], $text);
+                assert_eq!(attribute($input), Some(Ok(expected)));
+            }};
+        }
+
+        case!("@cli::entry" => Outer ["cli", "entry"] [] false);
+        case!("@test::run\n" => Outer ["test", "run"] [] false);
+        case!("@!cli::mode 'standard'" => Inner ["cli", "mode"] ["'standard'"] false);
+        case!("@!cli::help::desc 'Calculate fibonacci number'" => Inner ["cli", "help", "desc"] ["'Calculate fibonacci number'"] false);
+        case!("  @deprecated" => Outer ["deprecated"] [] false);
+        case!("@derive(Debug, Clone)" => Outer ["derive"] ["(", "Debug", ",", "Clone", ")"] false);
+        case!("@cli::help::params '''\n  n: the index\n'''\n" => Outer ["cli", "help", "params"] [] true);
+        case!("@!cli::mode 'standard' # comment" => Inner ["cli", "mode"] ["'standard'"] false);
+    }
+
+    #[test]
+    fn not_attribute() {
+        macro_rules! case {
+            ($input:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(attribute($input), None);
+            }};
+        }
+
+        case!("pub fn main");
+        case!("a @ b");
+        case!("@@desc Documentation.");
+        case!("@@!desc Documentation.");
+        case!("+= 1");
+    }
+
+    #[test]
+    fn negative() {
+        use crate::ast::LnCol;
+        use AttributeError::*;
+
+        macro_rules! case {
+            ($input:expr => $variant:ident $offset:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let at = Position::new($offset, LnCol::from_pred_counts(0, $offset));
+                assert_eq!(attribute($input), Some(Err($variant { at })));
+            }};
+        }
+
+        case!("@" => MissingPath 1);
+        case!("@!" => MissingPath 2);
+        case!("@ cli" => MissingPath 1);
+        case!("@'abc'" => MissingPath 1);
+        case!("@::cli" => MissingPath 1);
+        case!("@!::cli" => MissingPath 2);
+        case!("@cli::" => IncompletePath 6);
+        case!("@cli:: entry" => IncompletePath 6);
+        case!("@cli::help::'abc'" => IncompletePath 12);
+    }
+}
@@ -1,11 +1,14 @@
 mod comment;
 pub mod embed;
+mod shebang;
 
 pub use comment::CommentToken;
 pub use embed::{DocToken, TextToken};
+pub use shebang::ShebangToken;
 
 use super::IndentToken;
 use derive_more::{From, TryInto};
+use embed::doc::DocKind;
 use pipe_trait::Pipe;
 
 /// Token at the end of a line.
@@ -22,13 +25,23 @@ pub enum EndingToken<Content> {
 
     /// Documentation annotation.
     Doc(DocToken<Content>),
+
+    /// Interpreter directive.
+    ///
+    /// A shebang starts with the characters `#!` and is only recognized at the very start
+    /// of the first line, it is a [comment](EndingToken::Comment) elsewhere.
+    Shebang(ShebangToken<Content>),
 }
 
 impl<'input> EndingToken<&'input str> {
     /// Build an [`EndingToken`] from start to finish.
+    ///
+    /// `trailing` tells whether there are other tokens before `header_text` on the same line,
+    /// an outer documentation after them is a [trailing documentation](DocKind::Trailing).
     pub(crate) fn build<'header_indent>(
         header_indent: &'header_indent IndentToken,
         header_text: &'input str,
+        trailing: bool,
         mut next_line: impl FnMut() -> Option<&'input str>,
         mut after_parse: impl FnMut(&'input str),
     ) -> Option<Self> {
@@ -46,6 +59,13 @@ impl<'input> EndingToken<&'input str> {
             return comment.pipe(EndingToken::from).pipe(Some);
         }
         try_build!(TextToken);
+        if trailing {
+            let mut doc = DocToken::build(header_indent, header_text, || None, |_| {})?;
+            if doc.header.0.kind == DocKind::Outer {
+                doc.header.0.kind = DocKind::Trailing;
+            }
+            return doc.pipe(EndingToken::from).pipe(Some);
+        }
         try_build!(DocToken);
         None
     }
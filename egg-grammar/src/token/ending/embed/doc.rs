@@ -1,16 +1,58 @@
 use crate::token::{ParseEmbedTokenTag, ParseMiddleToken, RawToken, WordToken};
-use derive_more::{From, Into};
 use pipe_trait::Pipe;
 
 /// Token for chunk of documentation lines.
 pub type DocToken<Content> =
     super::EmbedToken<DocTokenTag<Content>, RawToken<Content>, RawToken<Content>>;
 
+/// Which item a [`DocToken`] documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocKind {
+    /// Documentation of the item after it (`@@name`).
+    Outer,
+    /// Documentation of the item that contains it, such as a module (`@@!name`).
+    Inner,
+    /// Documentation of the item on the same line, after other tokens
+    /// (`pub case Ok(X) @@desc Contain the success value.`).
+    ///
+    /// A trailing documentation has no body, the lines after it are not a part of it.
+    Trailing,
+}
+
 /// Tag of [`DocToken`].
 ///
-/// **Structure:** `@@[name]`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, From, Into)]
-pub struct DocTokenTag<Content>(pub Option<WordToken<Content>>);
+/// **Structure:** `@@[!][name]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocTokenTag<Content> {
+    /// Whether the documentation is outer (`@@`) or inner (`@@!`).
+    pub kind: DocKind,
+    /// Name of the documentation (e.g. `desc`, `params`).
+    pub name: Option<WordToken<Content>>,
+}
+
+impl<Content> DocTokenTag<Content> {
+    /// Create a tag of an outer documentation.
+    pub const fn outer(name: Option<WordToken<Content>>) -> Self {
+        DocTokenTag {
+            kind: DocKind::Outer,
+            name,
+        }
+    }
+
+    /// Create a tag of an inner documentation.
+    pub const fn inner(name: Option<WordToken<Content>>) -> Self {
+        DocTokenTag {
+            kind: DocKind::Inner,
+            name,
+        }
+    }
+}
+
+impl<Content> From<Option<WordToken<Content>>> for DocTokenTag<Content> {
+    fn from(name: Option<WordToken<Content>>) -> Self {
+        DocTokenTag::outer(name)
+    }
+}
 
 impl<Content> From<WordToken<Content>> for DocTokenTag<Content> {
     fn from(token: WordToken<Content>) -> Self {
@@ -27,11 +69,15 @@ impl<'a> From<&'a str> for DocTokenTag<&'a str> {
 impl<'a> ParseEmbedTokenTag<&'a str> for DocTokenTag<&'a str> {
     fn parse(input: &'a str) -> Option<(Self, &'a str)> {
         let input = input.strip_prefix("@@")?;
+        let (kind, input) = match input.strip_prefix('!') {
+            Some(input) => (DocKind::Inner, input),
+            None => (DocKind::Outer, input),
+        };
         let (name, rest) = match WordToken::parse(input) {
             Some((name, rest)) => (Some(name), rest),
             None => (None, input),
         };
-        let token = DocTokenTag(name);
+        let token = DocTokenTag { kind, name };
         Some((token, rest))
     }
 }
@@ -56,6 +102,10 @@ mod test {
         case!("@@. abcdef" -> None, ". abcdef");
         case!("@@ Nothing to see here" -> None, " Nothing to see here");
         case!("@@desc Description of an item" -> "desc", " Description of an item");
+        case!("@@!" -> DocTokenTag::inner(None), "");
+        case!("@@!desc Library to calculate fibonacci number." -> DocTokenTag::inner(Some("desc".into())), " Library to calculate fibonacci number.");
+        case!("@@! Nothing to see here" -> DocTokenTag::inner(None), " Nothing to see here");
+        case!("@@!!desc" -> DocTokenTag::inner(None), "!desc");
     }
 
     #[test]
@@ -1,4 +1,8 @@
-use crate::token::{ParseEmbedTokenTag, RawToken};
+use crate::{
+    ast::Span,
+    token::{ParseEmbedTokenTag, RawToken, TokenLine},
+};
+use derive_more::Error;
 use strum::{AsRefStr, Display, EnumString, IntoStaticStr};
 
 /// Token of multi-line string.
@@ -39,6 +43,170 @@ impl<'a> ParseEmbedTokenTag<&'a str> for TextTokenTag {
     }
 }
 
+/// Newline that joins the lines of the value of a [`TextToken`].
+///
+/// The newline is chosen by a flag in the header (e.g. `'''yaml crlf`) instead of
+/// the EOLs of the source text, so the value doesn't depend on how the file was saved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)] // essential std traits
+#[derive(AsRefStr, Display, EnumString, IntoStaticStr)] // essential strum traits
+pub enum NewlinePolicy {
+    /// Line Feed (`\n`), the default.
+    #[default]
+    #[strum(serialize = "lf")]
+    Lf,
+    /// Carriage Return followed by Line Feed (`\r\n`).
+    #[strum(serialize = "crlf")]
+    Crlf,
+}
+
+impl NewlinePolicy {
+    /// Get the name of the flag of the policy.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Get the newline characters.
+    pub const fn newline(self) -> &'static str {
+        match self {
+            NewlinePolicy::Lf => "\n",
+            NewlinePolicy::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Line after a multi-line string that was left out of its body because its indentation
+/// is deeper than the header but doesn't start with the indentation of the first body line.
+#[derive(Debug, derive_more::Display, Clone, Copy, PartialEq, Eq, Error)]
+#[display(
+    fmt = "Indentation of line {} doesn't start with the indentation of the multi-line string",
+    "span.start.ln_col.line"
+)]
+pub struct TextIndentMismatch {
+    /// Index of the line in the `following_lines` of [`TextToken::cook`].
+    pub index: usize,
+    /// Location of the indentation of the line.
+    pub span: Span,
+}
+
+/// Result of [`TextToken::cook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookedText {
+    /// Value of the multi-line string, see [`TextToken::value`].
+    pub value: String,
+    /// Lines after the body that were likely meant to be a part of it.
+    pub mismatches: Vec<TextIndentMismatch>,
+}
+
+impl<Content> TextToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Iterate over the whitespace-separated words of the header after the opening quotes.
+    fn attr_words(&self) -> impl Iterator<Item = &'_ str> + '_ {
+        let (_, RawToken(attr)) = &self.header;
+        attr.as_ref().split_whitespace()
+    }
+
+    /// Get the language tag after the opening quotes (e.g. `yaml` in `'''yaml crlf`).
+    ///
+    /// Return `None` if there is no tag.
+    pub fn language(&self) -> Option<&'_ str> {
+        self.attr_words()
+            .next()
+            .filter(|word| word.parse::<NewlinePolicy>().is_err())
+    }
+
+    /// Get the newline policy from the flags in the header.
+    ///
+    /// Return [`NewlinePolicy::Lf`] if there is no flag, return the last flag if there are many.
+    pub fn newline_policy(&self) -> NewlinePolicy {
+        self.attr_words()
+            .filter_map(|word| word.parse().ok())
+            .last()
+            .unwrap_or_default()
+    }
+
+    /// Iterate over the body lines without their common indentation.
+    ///
+    /// Lines that consist only of whitespaces are empty.
+    pub fn dedented_lines(&self) -> impl Iterator<Item = &'_ str> + '_ {
+        self.body.iter().map(|RawToken(line)| {
+            let line = line.as_ref();
+            match line.trim().is_empty() {
+                true => "",
+                false => line,
+            }
+        })
+    }
+
+    /// Get the value of the multi-line string with the [newline policy](TextToken::newline_policy) from the header.
+    pub fn value(&self) -> String {
+        self.value_with(self.newline_policy())
+    }
+
+    /// Get the value of the multi-line string with a chosen newline policy.
+    ///
+    /// * The indentation of the first body line is removed from every line, deeper indentation is kept.
+    /// * Trailing whitespaces of every line are removed.
+    /// * Blank lines before and between other lines are empty lines,
+    ///   blank lines after the last non-blank line are not a part of the value.
+    /// * Every line is terminated by the newline of the policy.
+    pub fn value_with(&self, policy: NewlinePolicy) -> String {
+        let lines: Vec<_> = self.dedented_lines().map(str::trim_end).collect();
+        let len = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |last| last + 1);
+        let mut value = String::new();
+        for line in &lines[..len] {
+            value.push_str(line);
+            value.push_str(policy.newline());
+        }
+        value
+    }
+
+    /// Get the value of the multi-line string and the lines that were likely meant to be a part of its body.
+    ///
+    /// * `line` is the token line that ends with the multi-line string.
+    /// * `following_lines` are the token lines after `line`.
+    ///
+    /// The body of a multi-line string ends at the first line whose indentation doesn't start with
+    /// the indentation of the first body line. Until a line that isn't indented deeper than `line`,
+    /// the lines whose indentation doesn't start with that of the first body line are reported as
+    /// [mismatches](CookedText::mismatches). Blank lines are skipped.
+    pub fn cook(
+        &self,
+        line: &TokenLine<Content>,
+        following_lines: &[TokenLine<Content>],
+    ) -> CookedText {
+        let value = self.value();
+        let src_lines = line.ending.iter().flat_map(|ending| &ending.src_text.1);
+        let first_body_indent = src_lines
+            .zip(&self.body)
+            .map(|((src_line, _), RawToken(body_line))| (src_line.as_ref(), body_line.as_ref()))
+            .find(|(_, body_line)| !body_line.trim().is_empty())
+            .map(|(src_line, body_line)| &src_line[..(src_line.len() - body_line.len())]);
+        let Some(first_body_indent) = first_body_indent else {
+            let mismatches = Vec::new();
+            return CookedText { value, mismatches };
+        };
+
+        let header_indent = &line.indent.token;
+        let mismatches = following_lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.middle.is_empty() || line.ending.is_some())
+            .take_while(|(_, line)| header_indent.is_shorter_start_of(&line.indent.token))
+            .filter(|(_, line)| !line.indent.src_text.as_ref().starts_with(first_body_indent))
+            .map(|(index, line)| TextIndentMismatch {
+                index,
+                span: line.indent.span,
+            })
+            .collect();
+        CookedText { value, mismatches }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -77,4 +245,112 @@ mod test {
         case!("''");
         case!("\"\"");
     }
+
+    fn text_token(text: &str) -> TextToken<&'_ str> {
+        use crate::token::{EndingToken, Scan};
+        let line = Scan::new(text).next().expect("text should have a line");
+        match line.ending.expect("line should have an ending token").token {
+            EndingToken::Text(token) => token,
+            token => panic!("{token:?} is not a multi-line string"),
+        }
+    }
+
+    #[test]
+    fn header() {
+        macro_rules! case {
+            ($input:literal -> $language:expr, $policy:ident) => {{
+                eprintln!("TEST: {:?}", $input);
+                let token = text_token($input);
+                assert_eq!(
+                    (token.language(), token.newline_policy()),
+                    ($language, NewlinePolicy::$policy),
+                );
+            }};
+        }
+
+        case!("let a = '''" -> None, Lf);
+        case!("let a = '''yaml" -> Some("yaml"), Lf);
+        case!("let a = '''yaml crlf" -> Some("yaml"), Crlf);
+        case!("let a = '''crlf" -> None, Crlf);
+        case!("let a = ''' js lf " -> Some("js"), Lf);
+        case!("let a = '''crlf lf" -> None, Lf);
+    }
+
+    #[test]
+    fn value() {
+        macro_rules! case {
+            ($input:literal -> $lf:literal, $crlf:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let token = text_token($input);
+                assert_eq!(token.value_with(NewlinePolicy::Lf), $lf);
+                assert_eq!(token.value_with(NewlinePolicy::Crlf), $crlf);
+            }};
+        }
+
+        case!("let a = '''" -> "", "");
+        case!("let a = '''\n    abc\n" -> "abc\n", "abc\r\n");
+        case!("let a = '''\n    abc\n\n      def\n  \n\n" -> "abc\n\n  def\n", "abc\r\n\r\n  def\r\n");
+        case!("let a = '''\r\n    abc\r\n    def\r\n" -> "abc\ndef\n", "abc\r\ndef\r\n");
+        case!("let a = '''\n\n    abc  \n    def\t\n" -> "\nabc\ndef\n", "\r\nabc\r\ndef\r\n");
+    }
+
+    #[test]
+    fn value_from_header() {
+        let lf = "let a = '''crlf\n    abc\n    def\n";
+        let crlf = "let a = '''crlf\r\n    abc\r\n    def\r\n";
+        assert_eq!(text_token(lf).value(), "abc\r\ndef\r\n");
+        assert_eq!(text_token(crlf).value(), "abc\r\ndef\r\n");
+        let lf = "let a = '''yaml\n    abc: def\n";
+        let crlf = "let a = '''yaml\r\n    abc: def\r\n";
+        assert_eq!(text_token(lf).value(), "abc: def\n");
+        assert_eq!(text_token(crlf).value(), "abc: def\n");
+    }
+
+    #[test]
+    fn cook() {
+        use crate::token::{EndingToken, Scan};
+
+        macro_rules! case {
+            ($input:literal -> $value:literal, [$($index:literal),*]) => {{
+                eprintln!("TEST: {:?}", $input);
+                let lines: Vec<_> = Scan::new($input).collect();
+                let (line, following_lines) = lines.split_first().expect("text should have a line");
+                let ending = line.ending.as_ref().expect("line should have an ending token");
+                let EndingToken::Text(token) = &ending.token else {
+                    panic!("{:?} is not a multi-line string", ending.token);
+                };
+                let CookedText { value, mismatches } = token.cook(line, following_lines);
+                let indices: Vec<_> = mismatches.iter().map(|mismatch| mismatch.index).collect();
+                assert_eq!((value.as_str(), indices), ($value, vec![$($index),*]));
+            }};
+        }
+
+        case!("let a = '''" -> "", []);
+        case!("let a = '''\n    abc\n      def\nprint(a)\n" -> "abc\n  def\n", []);
+        case!("    let a = '''crlf\n        abc\n    print(a)\n" -> "abc\r\n", []);
+        case!("let a = '''\n    abc\n  def\nprint(a)\n" -> "abc\n", [0]);
+        case!("let a = '''\n    abc\n  def\n   ghi\nprint(a)\n" -> "abc\n", [0, 1]);
+        case!("let a = '''\n    abc\n\tdef\n" -> "abc\n", [0]);
+        case!("  let a = '''\n    abc\n   def\n  print(a)\n" -> "abc\n", [0]);
+        case!("let a = '''\n    abc\nprint(a)\n  def\n" -> "abc\n", []);
+        case!("let a = '''\n    abc\n  def\n\n  ghi\nprint(a)\n" -> "abc\n", [0, 2]);
+    }
+
+    #[test]
+    fn display_mismatch() {
+        use crate::token::{EndingToken, Scan};
+        let lines: Vec<_> = Scan::new("let a = '''\n    abc\n  def\n").collect();
+        let EndingToken::Text(token) = &lines[0].ending.as_ref().unwrap().token else {
+            panic!("line should end with a multi-line string");
+        };
+        let received: Vec<_> = token
+            .cook(&lines[0], &lines[1..])
+            .mismatches
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let expected =
+            ["Indentation of line 3 doesn't start with the indentation of the multi-line string"];
+        assert_eq!(received, expected);
+    }
 }
@@ -0,0 +1,67 @@
+/// Token of the interpreter directive on the first line of a script (`#! /usr/bin/env egg`).
+///
+/// The content is the text after `#!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShebangToken<Content>(pub Content);
+
+impl<'a> ShebangToken<&'a str> {
+    /// Parse an input text into a shebang.
+    ///
+    /// **Note:** `input` is assumed to be the first line of the text and to not contain any EOL characters.
+    pub fn parse(input: &'a str) -> Option<Self> {
+        input.strip_prefix("#!").map(ShebangToken)
+    }
+}
+
+impl<Content> ShebangToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Path of the interpreter.
+    pub fn interpreter(&self) -> &'_ str {
+        self.0.as_ref().split_whitespace().next().unwrap_or("")
+    }
+
+    /// Arguments that are passed to the interpreter.
+    pub fn arguments(&self) -> impl Iterator<Item = &'_ str> + '_ {
+        self.0.as_ref().split_whitespace().skip(1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        macro_rules! case {
+            ($input:literal -> $interpreter:literal $($argument:literal)*) => {{
+                eprintln!("TEST: {:?}", $input);
+                let token = ShebangToken::parse($input).unwrap();
+                assert_eq!(token.0, &$input[2..]);
+                assert_eq!(token.interpreter(), $interpreter);
+                assert_eq!(token.arguments().collect::<Vec<_>>(), Vec::<&str>::from([$($argument),*]));
+            }};
+        }
+
+        case!("#! /usr/bin/env egg" -> "/usr/bin/env" "egg");
+        case!("#!/usr/bin/egg" -> "/usr/bin/egg");
+        case!("#!/usr/bin/env -S egg --strict" -> "/usr/bin/env" "-S" "egg" "--strict");
+        case!("#!" -> "");
+    }
+
+    #[test]
+    fn negative() {
+        macro_rules! case {
+            ($input:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(ShebangToken::parse($input), None);
+            }};
+        }
+
+        case!("");
+        case!("# comment");
+        case!(" #! /usr/bin/env egg");
+    }
+}
@@ -0,0 +1,134 @@
+use split_first_char::split_first_char;
+use strum::{AsRefStr, IntoStaticStr};
+
+/// Token of the character sequence at the end of a line.
+///
+/// **Specification:** `docs/specifications/misc/eol.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)] // essential std traits
+#[derive(AsRefStr, IntoStaticStr)] // essential strum traits
+pub enum EolToken {
+    /// Line Feed (`\n`).
+    #[strum(serialize = "\n")]
+    Lf,
+    /// Carriage Return followed by Line Feed (`\r\n`).
+    #[strum(serialize = "\r\n")]
+    Crlf,
+    /// Carriage Return that isn't followed by Line Feed (`\r`).
+    ///
+    /// This EOL is invalid, the scanner only ends the line here so that
+    /// the error can be reported instead of being hidden inside other tokens.
+    #[strum(serialize = "\r")]
+    Cr,
+}
+
+impl EolToken {
+    /// Get the string form of the token.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Check if the EOL is allowed by the specification.
+    pub const fn is_valid(self) -> bool {
+        !matches!(self, EolToken::Cr)
+    }
+
+    /// Check if a character may start an EOL.
+    pub const fn is_eol_head(char: &char) -> bool {
+        matches!(char, '\n' | '\r')
+    }
+
+    /// Parse an EOL at the start of the input text.
+    ///
+    /// **Return value:**
+    /// * `None` means the input does not start with an EOL.
+    /// * `Some((token, rest))` means that `token` is the EOL and `rest` is the remaining unparsed string.
+    pub fn parse(input: &str) -> Option<(Self, &'_ str)> {
+        match split_first_char(input)? {
+            ('\n', rest) => Some((EolToken::Lf, rest)),
+            ('\r', rest) => match rest.strip_prefix('\n') {
+                Some(rest) => Some((EolToken::Crlf, rest)),
+                None => Some((EolToken::Cr, rest)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Split the input text at the first EOL.
+    ///
+    /// **Return value:**
+    /// `(line, eol, rest)` where `line` is the text before the EOL, `eol` is the EOL
+    /// (`None` if there is no EOL), and `rest` is the text after the EOL.
+    pub fn split_line(input: &str) -> (&'_ str, Option<Self>, &'_ str) {
+        let Some(eol_offset) = input.find(|char| EolToken::is_eol_head(&char)) else {
+            return (input, None, "");
+        };
+        let line = &input[..eol_offset];
+        let (eol, rest) = EolToken::parse(&input[eol_offset..]).expect("EOL head was found");
+        (line, Some(eol), rest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        macro_rules! case {
+            ($input:literal -> $token:ident, $rest:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(EolToken::parse($input), Some((EolToken::$token, $rest)));
+            }};
+            ($input:literal -> None) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(EolToken::parse($input), None);
+            }};
+        }
+
+        case!("\n" -> Lf, "");
+        case!("\r\n" -> Crlf, "");
+        case!("\r" -> Cr, "");
+        case!("\n\r\n" -> Lf, "\r\n");
+        case!("\r\r\n" -> Cr, "\r\n");
+        case!("\r\nabc" -> Crlf, "abc");
+        case!("\rabc" -> Cr, "abc");
+        case!("" -> None);
+        case!("abc\n" -> None);
+        case!(" \n" -> None);
+    }
+
+    #[test]
+    fn split_line() {
+        use EolToken::*;
+
+        macro_rules! case {
+            ($input:literal -> $line:literal, $eol:expr, $rest:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(EolToken::split_line($input), ($line, $eol, $rest));
+            }};
+        }
+
+        case!("" -> "", None, "");
+        case!("abc" -> "abc", None, "");
+        case!("abc\ndef" -> "abc", Some(Lf), "def");
+        case!("abc\r\ndef" -> "abc", Some(Crlf), "def");
+        case!("abc\rdef" -> "abc", Some(Cr), "def");
+        case!("abc\r\r\ndef" -> "abc", Some(Cr), "\r\ndef");
+        case!("\n\n" -> "", Some(Lf), "\n");
+    }
+
+    #[test]
+    fn validity() {
+        assert!(EolToken::Lf.is_valid());
+        assert!(EolToken::Crlf.is_valid());
+        assert!(!EolToken::Cr.is_valid());
+    }
+
+    #[test]
+    fn as_str() {
+        assert_eq!(EolToken::Lf.as_str(), "\n");
+        assert_eq!(EolToken::Crlf.as_str(), "\r\n");
+        assert_eq!(EolToken::Cr.as_str(), "\r");
+    }
+}
@@ -0,0 +1,68 @@
+use super::{EndingToken, IndentToken, MiddleToken, TokenLine};
+
+/// Stack of the indentations of the enclosing blocks.
+///
+/// It is shared by [`Layout`](super::Layout) and [`analyze_indent`](super::analyze_indent)
+/// so that both see the same block structure.
+#[derive(Debug, Default)]
+pub(super) struct IndentStack(Vec<IndentToken>);
+
+/// Change of the block structure caused by a line, see [`IndentStack::push`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct IndentChange {
+    /// Number of blocks that end before the line.
+    pub dedents: usize,
+    /// Whether a new block starts at the line.
+    pub indents: bool,
+}
+
+impl IndentChange {
+    /// Check if the line is shallower than the current block but doesn't match any outer block.
+    ///
+    /// The stack recovers by starting a new block at the indentation of the line.
+    pub fn is_inconsistent(self) -> bool {
+        self.dedents > 0 && self.indents
+    }
+}
+
+impl IndentStack {
+    /// Update the stack with the indentation of a line that is not [blank](is_blank).
+    ///
+    /// Indentation levels are compared with [`IndentToken::is_shorter_start_of`].
+    pub fn push(&mut self, indent: &IndentToken) -> IndentChange {
+        let mut dedents = 0;
+        while let Some(current) = self.0.last() {
+            if current.is_start_of(indent) {
+                break;
+            }
+            self.0.pop();
+            dedents += 1;
+        }
+
+        let indents = self.0.last().map_or(!indent.is_empty(), |current| {
+            current.is_shorter_start_of(indent)
+        });
+        if indents {
+            self.0.push(indent.clone());
+        }
+
+        IndentChange { dedents, indents }
+    }
+
+    /// End the innermost block.
+    pub fn pop(&mut self) -> Option<IndentToken> {
+        self.0.pop()
+    }
+}
+
+/// Check if a line is blank or only has a comment.
+pub(super) fn is_blank<Content>(line: &TokenLine<Content>) -> bool {
+    let is_whitespace = |item: &_| matches!(item, Ok(MiddleToken::Whitespace(_)));
+    line.middle.iter().all(|item| is_whitespace(&item.token))
+        && line.ending.as_ref().map_or(true, |ending| {
+            matches!(
+                ending.token,
+                EndingToken::Comment(_) | EndingToken::Shebang(_)
+            )
+        })
+}
@@ -0,0 +1,253 @@
+use super::{MapContent, MiddleToken, Scan, TokenLine, WordToken};
+use crate::keyword::Keyword;
+use std::{collections::HashMap, sync::Arc};
+use strum::IntoEnumIterator;
+
+/// Handle of a string in an [`Interner`].
+///
+/// Comparing and hashing symbols is cheaper than comparing and hashing the strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Position of the string in the interner.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Symbol table that maps every distinct string to a [`Symbol`].
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    strings: Vec<Arc<str>>,
+    symbols: HashMap<Arc<str>, Symbol>,
+    has_keywords: bool,
+}
+
+impl Interner {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Create an interner that is pre-seeded with every [`Keyword`].
+    ///
+    /// The symbol of a keyword is the same in every interner that was created by this function,
+    /// see [`Interner::keyword_symbol`].
+    pub fn with_keywords() -> Self {
+        let mut interner = Interner::new();
+        for keyword in Keyword::iter() {
+            let symbol = interner.intern(keyword.as_ref());
+            debug_assert_eq!(symbol, Symbol(keyword as u32));
+        }
+        interner.has_keywords = true;
+        interner
+    }
+
+    /// Number of interned strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Check if no strings were interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Get the symbol of a string, add the string if it wasn't interned.
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        if let Some(symbol) = self.get(text) {
+            return symbol;
+        }
+        let index = u32::try_from(self.strings.len()).expect("too many interned strings");
+        let symbol = Symbol(index);
+        let text: Arc<str> = Arc::from(text);
+        self.strings.push(text.clone());
+        self.symbols.insert(text, symbol);
+        symbol
+    }
+
+    /// Get the symbol of a string that was interned.
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.symbols.get(text).copied()
+    }
+
+    /// Get the string of a symbol.
+    ///
+    /// Return `None` if the symbol belongs to another interner.
+    pub fn resolve(&self, symbol: Symbol) -> Option<&'_ str> {
+        self.strings.get(symbol.index()).map(AsRef::as_ref)
+    }
+
+    /// Get the symbol of a keyword without hashing.
+    ///
+    /// Return `None` if the interner wasn't [pre-seeded with keywords](Interner::with_keywords).
+    pub fn keyword_symbol(&self, keyword: Keyword) -> Option<Symbol> {
+        self.has_keywords.then_some(Symbol(keyword as u32))
+    }
+
+    /// Get the keyword of a symbol without hashing.
+    ///
+    /// Return `None` if the symbol isn't a keyword or the interner wasn't
+    /// [pre-seeded with keywords](Interner::with_keywords).
+    pub fn keyword(&self, symbol: Symbol) -> Option<Keyword> {
+        if !self.has_keywords {
+            return None;
+        }
+        Keyword::from_repr(symbol.index())
+    }
+
+    /// Intern a word and classify it as a keyword or an identifier.
+    ///
+    /// If the interner was [pre-seeded with keywords](Interner::with_keywords), the word is only
+    /// hashed once, otherwise it is also looked up in the table of keywords.
+    pub fn intern_word(&mut self, word: &str) -> WordToken<Symbol> {
+        if !self.has_keywords {
+            return WordToken::from_any_str(word).map_content(|word| self.intern(word));
+        }
+        let symbol = self.intern(word);
+        match self.keyword(symbol) {
+            Some(keyword) => WordToken::Keyword(keyword),
+            None => WordToken::Identifier(symbol),
+        }
+    }
+}
+
+/// Content of a [`TokenLine`] that was scanned by [`Scan::intern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interned<'a> {
+    /// The scanned text.
+    pub text: &'a str,
+    /// Symbol of the text if it is an identifier.
+    pub symbol: Option<Symbol>,
+}
+
+impl<'a> AsRef<str> for Interned<'a> {
+    fn as_ref(&self) -> &str {
+        self.text
+    }
+}
+
+impl<'a> Scan<'a> {
+    /// Intern the identifiers of the scanned lines.
+    ///
+    /// Every [`WordToken::Identifier`] carries its [`Symbol`], other contents of the lines are kept
+    /// as they are and are not added to the interner.
+    pub fn intern<'b>(
+        self,
+        interner: &'b mut Interner,
+    ) -> impl Iterator<Item = TokenLine<Interned<'a>>> + 'b
+    where
+        'a: 'b,
+    {
+        self.map(|line| {
+            let mut line = line.map_content(|text| Interned { text, symbol: None });
+            for item in &mut line.middle {
+                if let Ok(MiddleToken::Word(WordToken::Identifier(identifier))) = &mut item.token {
+                    identifier.symbol = Some(interner.intern(identifier.text));
+                }
+            }
+            line
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use strum::EnumCount;
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        let abc = interner.intern("abc");
+        let def = interner.intern("def");
+        assert_ne!(abc, def);
+        assert_eq!(interner.intern("abc"), abc);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("def"), Some(def));
+        assert_eq!(interner.get("ghi"), None);
+        assert_eq!(interner.resolve(abc), Some("abc"));
+        assert_eq!(interner.resolve(def), Some("def"));
+        assert_eq!(interner.resolve(Symbol(2)), None);
+    }
+
+    #[test]
+    fn keywords() {
+        let interner = Interner::with_keywords();
+        assert_eq!(interner.len(), Keyword::COUNT);
+        for keyword in Keyword::iter() {
+            eprintln!("TEST: {keyword:?}");
+            let symbol = interner.keyword_symbol(keyword).unwrap();
+            assert_eq!(interner.get(keyword.as_ref()), Some(symbol));
+            assert_eq!(interner.resolve(symbol), Some(keyword.as_ref()));
+            assert_eq!(interner.keyword(symbol), Some(keyword));
+        }
+        let symbol = Symbol(Keyword::COUNT as u32);
+        assert_eq!(interner.keyword(symbol), None);
+        assert_eq!(Interner::new().keyword_symbol(Keyword::If), None);
+        assert_eq!(Interner::new().keyword(Symbol(0)), None);
+    }
+
+    #[test]
+    fn intern_word() {
+        for mut interner in [Interner::new(), Interner::with_keywords()] {
+            let abc = interner.intern_word("abc");
+            assert_eq!(
+                interner.intern_word("return"),
+                WordToken::Keyword(Keyword::Return)
+            );
+            assert_eq!(interner.intern_word("abc"), abc);
+            let WordToken::Identifier(symbol) = abc else {
+                panic!("{abc:?} is not an identifier");
+            };
+            assert_eq!(interner.resolve(symbol), Some("abc"));
+            assert_eq!(interner.keyword(symbol), None);
+        }
+    }
+
+    #[test]
+    fn scan() {
+        let text = "let abc = 'text' # comment\nprint(abc, def)\n";
+        let mut interner = Interner::with_keywords();
+        let lines: Vec<_> = Scan::new(text).intern(&mut interner).collect();
+        assert_eq!(interner.len(), Keyword::COUNT + 3);
+
+        let words: Vec<_> = lines
+            .iter()
+            .flat_map(|line| &line.middle)
+            .filter_map(|item| match item.token {
+                Ok(MiddleToken::Word(word)) => Some((item.src_text.text, word)),
+                _ => None,
+            })
+            .collect();
+        let resolved: Vec<_> = words
+            .iter()
+            .map(|(src_text, word)| {
+                let word = word.map_content(|content| {
+                    let symbol = content.symbol.expect("identifier should be interned");
+                    interner.resolve(symbol).unwrap()
+                });
+                (*src_text, word)
+            })
+            .collect();
+        let expected = [
+            ("let", WordToken::Keyword(Keyword::Let)),
+            ("abc", WordToken::Identifier("abc")),
+            ("print", WordToken::Identifier("print")),
+            ("abc", WordToken::Identifier("abc")),
+            ("def", WordToken::Identifier("def")),
+        ];
+        assert_eq!(resolved, expected);
+        assert_eq!(words[1].1, words[3].1);
+        assert_ne!(words[1].1, words[4].1);
+
+        let lines: Vec<_> = lines
+            .into_iter()
+            .map(|line| line.map_content(|content| content.text))
+            .collect();
+        assert_eq!(lines, Scan::new(text).collect::<Vec<_>>());
+    }
+}
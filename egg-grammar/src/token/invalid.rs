@@ -1,14 +1,71 @@
+mod bidi;
+mod confusable;
+
+pub use bidi::*;
+pub use confusable::*;
+
 use derive_more::Error;
 use std::fmt::{self, Display, Formatter};
 
-/// Token represents an invalid (unparsable) character.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
-pub struct InvalidToken(#[error(not(source))] pub char);
+/// Token represents a run of invalid (unparsable) characters.
+///
+/// The run always consists of whole extended grapheme clusters,
+/// the code points of the clusters are listed in order.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct InvalidToken(#[error(not(source))] pub Vec<char>);
 
 impl Display for InvalidToken {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let InvalidToken(char) = self;
-        let code = *char as u32;
-        write!(f, "Unexpected token {char:?} (U+{code:04X})")
+        let InvalidToken(chars) = self;
+        match chars.as_slice() {
+            [char] => write!(f, "Unexpected token {char:?}")?,
+            _ => write!(f, "Unexpected token {:?}", String::from_iter(chars))?,
+        }
+        let mut codes = chars.iter().map(|char| *char as u32);
+        if let Some(code) = codes.next() {
+            write!(f, " (U+{code:04X}")?;
+            for code in codes {
+                write!(f, " U+{code:04X}")?;
+            }
+            write!(f, ")")?;
+        }
+        if let [_] = chars.as_slice() {
+            if let Some(confusable) = self.confusables().next() {
+                write!(f, ", ")?;
+                confusable.fmt_hint(f)?;
+            }
+        } else {
+            for confusable in self.confusables() {
+                write!(f, ", {confusable}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn display() {
+        macro_rules! case {
+            ($input:expr => $expected:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(
+                    InvalidToken($input.chars().collect()).to_string(),
+                    $expected
+                );
+            }};
+        }
+
+        case!("❤" => "Unexpected token '❤' (U+2764)");
+        case!("\0" => "Unexpected token '\\0' (U+0000)");
+        case!("❤\u{fe0f}" => "Unexpected token \"❤\\u{fe0f}\" (U+2764 U+FE0F)");
+        case!("👨\u{200d}👩" => "Unexpected token \"👨\\u{200d}👩\" (U+1F468 U+200D U+1F469), '\\u{200d}' (U+200D) is invisible");
+        case!("“" => "Unexpected token '“' (U+201C), looks like '\"'");
+        case!("\u{200B}" => "Unexpected token '\\u{200b}' (U+200B), is invisible");
+        case!("（“" => "Unexpected token \"（“\" (U+FF08 U+201C), '（' (U+FF08) looks like '(', '“' (U+201C) looks like '\"'");
     }
 }
@@ -0,0 +1,86 @@
+use super::{Confusable, ConfusableKind};
+use crate::{ast::Span, token::TokenLine};
+use derive_more::{Display, Error};
+use std::iter::once;
+
+/// Bidirectional control character anywhere in the text, including strings and comments.
+///
+/// Such characters could make the text appear different from how it is interpreted
+/// (a.k.a. [Trojan Source](https://trojansource.codes/)).
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+#[display(fmt = "{}: {}", "span.start.ln_col", confusable)]
+pub struct BidiControl {
+    /// Location of the character.
+    pub span: Span,
+    /// The character.
+    #[error(not(source))]
+    pub confusable: Confusable,
+}
+
+impl<Content> TokenLine<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Find all bidirectional control characters in the line and the body of its ending token.
+    pub fn bidi_controls(&self) -> impl Iterator<Item = BidiControl> + '_ {
+        let body_spans = self.ending.iter().flat_map(|ending| ending.body_spans());
+        let body_starts = body_spans.map(|span| span.start);
+        once(self.indent.span.start)
+            .chain(body_starts)
+            .zip(self.all_ln_text())
+            .flat_map(|(start, text)| {
+                let text = text.as_ref();
+                text.char_indices().filter_map(move |(offset, char)| {
+                    let confusable = Confusable::lookup(char)?;
+                    if confusable.kind != ConfusableKind::Bidi {
+                        return None;
+                    }
+                    let start = start.advance_inline(&text[..offset]);
+                    let span = Span::inline(start, &text[offset..(offset + char.len_utf8())]);
+                    Some(BidiControl { span, confusable })
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::token::Scan;
+    use pretty_assertions::assert_eq;
+
+    fn bidi_controls(text: &str) -> Vec<String> {
+        Scan::new(text)
+            .flat_map(|line| line.bidi_controls().collect::<Vec<_>>())
+            .map(|bidi| bidi.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn trojan_source() {
+        let text = [
+            "let access_level = \"user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}\"",
+            "if access_level != \"user\" then # \u{202E}comment",
+            "    print('''",
+            "        a\u{2067}b",
+            "    ''')",
+            "let c = a\u{202A}b",
+        ]
+        .join("\n");
+        let expected = [
+            "1:25: '\\u{202e}' (U+202E) changes the direction of the text",
+            "1:27: '\\u{2066}' (U+2066) changes the direction of the text",
+            "1:45: '\\u{2069}' (U+2069) changes the direction of the text",
+            "1:47: '\\u{2066}' (U+2066) changes the direction of the text",
+            "2:34: '\\u{202e}' (U+202E) changes the direction of the text",
+            "4:10: '\\u{2067}' (U+2067) changes the direction of the text",
+            "6:10: '\\u{202a}' (U+202A) changes the direction of the text",
+        ];
+        assert_eq!(bidi_controls(&text), expected);
+    }
+
+    #[test]
+    fn no_bidi_controls() {
+        let text = "let a = \"\u{200F}abc\u{200B}\" # “comment”\n";
+        assert_eq!(bidi_controls(text), Vec::<String>::new());
+    }
+}
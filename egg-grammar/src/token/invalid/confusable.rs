@@ -0,0 +1,163 @@
+use super::InvalidToken;
+use std::fmt::{self, Display, Formatter};
+
+/// Why a character is [confusable](Confusable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfusableKind {
+    /// The character looks like a character of the valid syntax.
+    Lookalike,
+    /// The character is invisible.
+    Invisible,
+    /// The character changes the direction of the text after it,
+    /// which could make the text appear different from how it is interpreted.
+    Bidi,
+}
+
+/// Character that is likely put in the text by mistake or to deceive the reader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confusable {
+    /// The confusable character.
+    pub char: char,
+    /// Why the character is confusable.
+    pub kind: ConfusableKind,
+    /// ASCII character that was likely intended (`None` if the character should be removed).
+    pub suggestion: Option<char>,
+}
+
+impl Confusable {
+    /// Look up a character in the table of confusable characters.
+    pub fn lookup(char: char) -> Option<Self> {
+        use ConfusableKind::*;
+        let (kind, suggestion) = match char {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => {
+                (Lookalike, Some('\''))
+            }
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => {
+                (Lookalike, Some('"'))
+            }
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2212}'
+            | '\u{FE63}' => (Lookalike, Some('-')),
+            '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => {
+                (Lookalike, Some(' '))
+            }
+            '\u{2044}' | '\u{2215}' => (Lookalike, Some('/')),
+            '\u{2217}' => (Lookalike, Some('*')),
+            '\u{037E}' => (Lookalike, Some(';')),
+            '\u{2024}' => (Lookalike, Some('.')),
+            '\u{FF01}'..='\u{FF5E}' => (Lookalike, char::from_u32(char as u32 - 0xFEE0)), // full-width forms of ASCII characters
+            '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => {
+                (Invisible, None)
+            }
+            '\u{200E}' | '\u{200F}' | '\u{061C}' => (Invisible, None),
+            '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => (Bidi, None),
+            _ => return None,
+        };
+        Some(Confusable {
+            char,
+            kind,
+            suggestion,
+        })
+    }
+}
+
+impl Confusable {
+    /// Explain why the character is confusable without naming it (e.g. `looks like '"'`).
+    pub(super) fn fmt_hint(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.kind, self.suggestion) {
+            (ConfusableKind::Lookalike, Some(suggestion)) => write!(f, "looks like {suggestion:?}"),
+            (ConfusableKind::Lookalike, None) => write!(f, "looks like a valid character"),
+            (ConfusableKind::Invisible, _) => write!(f, "is invisible"),
+            (ConfusableKind::Bidi, _) => write!(f, "changes the direction of the text"),
+        }
+    }
+}
+
+impl Display for Confusable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let char = self.char;
+        let code = char as u32;
+        write!(f, "{char:?} (U+{code:04X}) ")?;
+        self.fmt_hint(f)
+    }
+}
+
+impl InvalidToken {
+    /// Look up the characters in the table of [confusable](Confusable) characters.
+    pub fn confusables(&self) -> impl Iterator<Item = Confusable> + '_ {
+        let InvalidToken(chars) = self;
+        chars.iter().copied().filter_map(Confusable::lookup)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn lookup() {
+        macro_rules! case {
+            ($input:literal -> $kind:ident $suggestion:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(
+                    InvalidToken(vec![$input]).confusables().collect::<Vec<_>>(),
+                    [Confusable {
+                        char: $input,
+                        kind: ConfusableKind::$kind,
+                        suggestion: $suggestion,
+                    }],
+                );
+            }};
+        }
+
+        case!('‘' -> Lookalike Some('\''));
+        case!('’' -> Lookalike Some('\''));
+        case!('“' -> Lookalike Some('"'));
+        case!('”' -> Lookalike Some('"'));
+        case!('−' -> Lookalike Some('-'));
+        case!('–' -> Lookalike Some('-'));
+        case!('\u{A0}' -> Lookalike Some(' '));
+        case!('\u{3000}' -> Lookalike Some(' '));
+        case!('（' -> Lookalike Some('('));
+        case!('］' -> Lookalike Some(']'));
+        case!('｛' -> Lookalike Some('{'));
+        case!('，' -> Lookalike Some(','));
+        case!('；' -> Lookalike Some(';'));
+        case!('\u{37E}' -> Lookalike Some(';'));
+        case!('\u{200B}' -> Invisible None);
+        case!('\u{FEFF}' -> Invisible None);
+        case!('\u{200F}' -> Invisible None);
+        case!('\u{202E}' -> Bidi None);
+        case!('\u{2066}' -> Bidi None);
+    }
+
+    #[test]
+    fn not_confusable() {
+        macro_rules! case {
+            ($input:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(InvalidToken(vec![$input]).confusables().count(), 0);
+            }};
+        }
+
+        case!('❤');
+        case!('đ');
+        case!('長');
+        case!('\u{FF5F}');
+    }
+
+    #[test]
+    fn display() {
+        macro_rules! case {
+            ($input:literal -> $expected:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let confusable = Confusable::lookup($input).unwrap();
+                assert_eq!(confusable.to_string(), $expected);
+            }};
+        }
+
+        case!('“' -> "'“' (U+201C) looks like '\"'");
+        case!('\u{200B}' -> "'\\u{200b}' (U+200B) is invisible");
+        case!('\u{202E}' -> "'\\u{202e}' (U+202E) changes the direction of the text");
+    }
+}
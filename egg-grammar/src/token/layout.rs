@@ -0,0 +1,266 @@
+use super::{
+    indent_stack::{is_blank, IndentStack},
+    Scan, TokenLine,
+};
+use crate::ast::Span;
+use derive_more::{Display, Error};
+use std::collections::VecDeque;
+
+/// Token of [`Layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutToken<Content> {
+    /// Line that has tokens other than whitespaces and comments.
+    Line(TokenLine<Content>),
+    /// Line that is blank or only has a comment, it doesn't affect the block structure.
+    Blank(TokenLine<Content>),
+    /// End of a [line](LayoutToken::Line).
+    Newline,
+    /// Start of a block, the line after it is indented deeper than the line before it.
+    Indent,
+    /// End of a block.
+    Dedent,
+}
+
+/// Error of a line whose indentation is shallower than the current block but doesn't match any outer block.
+///
+/// The [`Layout`] recovers by starting a new block at the indentation of the line.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+#[display(
+    fmt = "Indentation of line {} doesn't match any outer block",
+    "span.start.ln_col.line"
+)]
+pub struct InconsistentDedent {
+    /// Location of the indentation of the line.
+    pub span: Span,
+}
+
+/// Adapter that adds [`Indent`](LayoutToken::Indent), [`Dedent`](LayoutToken::Dedent),
+/// and [`Newline`](LayoutToken::Newline) tokens to a sequence of [`TokenLine`].
+///
+/// Indentation levels are compared with [`IndentToken::is_shorter_start_of`](super::IndentToken::is_shorter_start_of).
+#[derive(Debug)]
+pub struct Layout<Content, Lines> {
+    lines: Lines,
+    stack: IndentStack,
+    queue: VecDeque<Result<LayoutToken<Content>, InconsistentDedent>>,
+}
+
+impl<Content, Lines> Layout<Content, Lines>
+where
+    Lines: Iterator<Item = TokenLine<Content>>,
+{
+    /// Start tracking the block structure of a sequence of token lines.
+    pub fn new(lines: Lines) -> Self {
+        Layout {
+            lines,
+            stack: IndentStack::default(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Add the tokens of a line to the queue.
+    fn push_line(&mut self, line: TokenLine<Content>) {
+        if is_blank(&line) {
+            self.queue.push_back(Ok(LayoutToken::Blank(line)));
+            return;
+        }
+
+        let change = self.stack.push(&line.indent.token);
+        for _ in 0..change.dedents {
+            self.queue.push_back(Ok(LayoutToken::Dedent));
+        }
+        if change.is_inconsistent() {
+            let span = line.indent.span;
+            self.queue.push_back(Err(InconsistentDedent { span }));
+        }
+        if change.indents {
+            self.queue.push_back(Ok(LayoutToken::Indent));
+        }
+
+        self.queue.push_back(Ok(LayoutToken::Line(line)));
+        self.queue.push_back(Ok(LayoutToken::Newline));
+    }
+}
+
+impl<Content, Lines> Iterator for Layout<Content, Lines>
+where
+    Lines: Iterator<Item = TokenLine<Content>>,
+{
+    type Item = Result<LayoutToken<Content>, InconsistentDedent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.queue.pop_front() {
+                return Some(token);
+            }
+            match self.lines.next() {
+                Some(line) => self.push_line(line),
+                None => {
+                    self.stack.pop()?;
+                    return Some(Ok(LayoutToken::Dedent));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Scan<'a> {
+    /// Add [`Indent`](LayoutToken::Indent), [`Dedent`](LayoutToken::Dedent),
+    /// and [`Newline`](LayoutToken::Newline) tokens to the scanned lines.
+    pub fn layout(self) -> Layout<&'a str, Self> {
+        Layout::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Summarize the layout tokens of a text as short strings.
+    fn layout(text: &str) -> Vec<String> {
+        Scan::new(text)
+            .layout()
+            .map(|token| match token {
+                Ok(LayoutToken::Line(line)) => format!("Line({})", line.ln_text.trim()),
+                Ok(LayoutToken::Blank(line)) => format!("Blank({})", line.ln_text.trim()),
+                Ok(LayoutToken::Newline) => "Newline".to_string(),
+                Ok(LayoutToken::Indent) => "Indent".to_string(),
+                Ok(LayoutToken::Dedent) => "Dedent".to_string(),
+                Err(error) => format!("Err({error})"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flat() {
+        let received = layout("let a = 1\nlet b = 2\n");
+        let expected = ["Line(let a = 1)", "Newline", "Line(let b = 2)", "Newline"];
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn nested() {
+        let text = [
+            "pub fn main() do",
+            "    if a then",
+            "        print(a)",
+            "    print(b)",
+            "print(c)",
+            "pub fn other() do",
+            "\tprint(d)",
+        ]
+        .join("\n");
+        let expected = [
+            "Line(pub fn main() do)",
+            "Newline",
+            "Indent",
+            "Line(if a then)",
+            "Newline",
+            "Indent",
+            "Line(print(a))",
+            "Newline",
+            "Dedent",
+            "Line(print(b))",
+            "Newline",
+            "Dedent",
+            "Line(print(c))",
+            "Newline",
+            "Line(pub fn other() do)",
+            "Newline",
+            "Indent",
+            "Line(print(d))",
+            "Newline",
+            "Dedent",
+        ];
+        assert_eq!(layout(&text), expected);
+    }
+
+    #[test]
+    fn blank_and_comment() {
+        let text = [
+            "#! /usr/bin/env egg",
+            "pub fn main() do",
+            "",
+            "# comment at the start of the line",
+            "        # deeply indented comment",
+            "    print(a) # trailing comment",
+            "  ",
+        ]
+        .join("\n");
+        let expected = [
+            "Blank(#! /usr/bin/env egg)",
+            "Line(pub fn main() do)",
+            "Newline",
+            "Blank()",
+            "Blank(# comment at the start of the line)",
+            "Blank(# deeply indented comment)",
+            "Indent",
+            "Line(print(a) # trailing comment)",
+            "Newline",
+            "Blank()",
+            "Dedent",
+        ];
+        assert_eq!(layout(&text), expected);
+    }
+
+    #[test]
+    fn text_block() {
+        let text = "pub fn main() do\n    let a = '''\n        abc\n\n          def\nprint(a)\n";
+        let expected = [
+            "Line(pub fn main() do)",
+            "Newline",
+            "Indent",
+            "Line(let a = ''')",
+            "Newline",
+            "Dedent",
+            "Line(print(a))",
+            "Newline",
+        ];
+        assert_eq!(layout(text), expected);
+    }
+
+    #[test]
+    fn inconsistent_dedent() {
+        let text = "if a then\n    if b then\n        print(b)\n  print(a)\nprint(c)\n";
+        let expected = [
+            "Line(if a then)",
+            "Newline",
+            "Indent",
+            "Line(if b then)",
+            "Newline",
+            "Indent",
+            "Line(print(b))",
+            "Newline",
+            "Dedent",
+            "Dedent",
+            "Err(Indentation of line 4 doesn't match any outer block)",
+            "Indent",
+            "Line(print(a))",
+            "Newline",
+            "Dedent",
+            "Line(print(c))",
+            "Newline",
+        ];
+        assert_eq!(layout(text), expected);
+    }
+
+    #[test]
+    fn mixed_indent_chars() {
+        let text = "if a then\n    print(a)\n\tprint(b)\n";
+        let expected = [
+            "Line(if a then)",
+            "Newline",
+            "Indent",
+            "Line(print(a))",
+            "Newline",
+            "Dedent",
+            "Err(Indentation of line 3 doesn't match any outer block)",
+            "Indent",
+            "Line(print(b))",
+            "Newline",
+            "Dedent",
+        ];
+        assert_eq!(layout(text), expected);
+    }
+}
@@ -0,0 +1,350 @@
+use super::{
+    embed::{doc::DocTokenTag, text::TextTokenTag, EmbedToken},
+    number::{
+        BinaryToken, DecimalToken, FractionalToken, HexadecimalToken, IntegerToken,
+        NumberTokenBody, NumberTokenSuffix, OctalToken,
+    },
+    CommentToken, EndingToken, LayoutToken, MiddleToken, NumberToken, OperatorToken, RawToken,
+    ShebangToken, StringToken, TokenLine, TokenLineItem, WhitespaceToken, WordToken,
+};
+
+/// Replace the content type of a token.
+///
+/// **Example:** Convert a `TokenLine<&str>` that borrows the scanned text into a `TokenLine<String>`
+/// or a `TokenLine<Arc<str>>` that can outlive the text and be sent to other threads.
+pub trait MapContent<Content, NewContent> {
+    /// The same type with `NewContent` in place of `Content`.
+    type Output;
+
+    /// Convert every content of the token with `map`.
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent;
+}
+
+macro_rules! impl_newtype {
+    ($($name:ident),* $(,)?) => {$(
+        impl<Content, NewContent> MapContent<Content, NewContent> for $name<Content> {
+            type Output = $name<NewContent>;
+            fn map_content<Map>(self, mut map: Map) -> Self::Output
+            where
+                Map: FnMut(Content) -> NewContent,
+            {
+                $name(map(self.0))
+            }
+        }
+    )*};
+}
+
+impl_newtype!(
+    WhitespaceToken,
+    OperatorToken,
+    DecimalToken,
+    BinaryToken,
+    OctalToken,
+    HexadecimalToken,
+    NumberTokenSuffix,
+    CommentToken,
+    ShebangToken,
+    RawToken,
+);
+
+impl<Content, NewContent> MapContent<Content, NewContent> for WordToken<Content> {
+    type Output = WordToken<NewContent>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            WordToken::Identifier(identifier) => WordToken::Identifier(map(identifier)),
+            WordToken::Keyword(keyword) => WordToken::Keyword(keyword),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for IntegerToken<Content> {
+    type Output = IntegerToken<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            IntegerToken::Decimal(token) => token.map_content(map).into(),
+            IntegerToken::Binary(token) => token.map_content(map).into(),
+            IntegerToken::Octal(token) => token.map_content(map).into(),
+            IntegerToken::Hexadecimal(token) => token.map_content(map).into(),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for FractionalToken<Content> {
+    type Output = FractionalToken<NewContent>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let FractionalToken {
+            integer,
+            fraction,
+            exponent,
+        } = self;
+        FractionalToken {
+            integer: map(integer),
+            fraction: fraction.map(&mut map),
+            exponent: exponent.map(&mut map),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for NumberTokenBody<Content> {
+    type Output = NumberTokenBody<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            NumberTokenBody::Integer(token) => token.map_content(map).into(),
+            NumberTokenBody::Fractional(token) => token.map_content(map).into(),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for NumberToken<Content> {
+    type Output = NumberToken<NewContent>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let NumberToken { body, suffix } = self;
+        NumberToken {
+            body: body.map_content(&mut map),
+            suffix: suffix.map(|suffix| suffix.map_content(&mut map)),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for StringToken<Content> {
+    type Output = StringToken<NewContent>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let StringToken {
+            prefix,
+            suffix,
+            body,
+            quote,
+            error,
+        } = self;
+        StringToken {
+            prefix: map(prefix),
+            suffix: map(suffix),
+            body: map(body),
+            quote,
+            error,
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for MiddleToken<Content> {
+    type Output = MiddleToken<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            MiddleToken::Whitespace(token) => token.map_content(map).into(),
+            MiddleToken::String(token) => token.map_content(map).into(),
+            MiddleToken::Word(token) => token.map_content(map).into(),
+            MiddleToken::Number(token) => token.map_content(map).into(),
+            MiddleToken::Bracket(token) => token.into(),
+            MiddleToken::Punctuation(token) => token.into(),
+            MiddleToken::Operator(token) => token.map_content(map).into(),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for TextTokenTag {
+    type Output = TextTokenTag;
+    fn map_content<Map>(self, _: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        self
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for DocTokenTag<Content> {
+    type Output = DocTokenTag<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let DocTokenTag { kind, name } = self;
+        let name = name.map(|name| name.map_content(map));
+        DocTokenTag { kind, name }
+    }
+}
+
+impl<Content, NewContent, Tag, Attr, Body> MapContent<Content, NewContent>
+    for EmbedToken<Tag, Attr, Body>
+where
+    Tag: MapContent<Content, NewContent>,
+    Attr: MapContent<Content, NewContent>,
+    Body: MapContent<Content, NewContent>,
+{
+    type Output = EmbedToken<Tag::Output, Attr::Output, Body::Output>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let EmbedToken {
+            header: (tag, attr),
+            body,
+        } = self;
+        let header = (tag.map_content(&mut map), attr.map_content(&mut map));
+        let body = body
+            .into_iter()
+            .map(|line| line.map_content(&mut map))
+            .collect();
+        EmbedToken { header, body }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for EndingToken<Content> {
+    type Output = EndingToken<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            EndingToken::Comment(token) => token.map_content(map).into(),
+            EndingToken::Text(token) => token.map_content(map).into(),
+            EndingToken::Doc(token) => token.map_content(map).into(),
+            EndingToken::Shebang(token) => token.map_content(map).into(),
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for TokenLine<Content> {
+    type Output = TokenLine<NewContent>;
+    fn map_content<Map>(self, mut map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        let TokenLine {
+            ln_text,
+            eol,
+            indent,
+            middle,
+            ending,
+        } = self;
+        let ln_text = map(ln_text);
+        let indent = TokenLineItem::new(map(indent.src_text), indent.token, indent.span);
+        let middle = middle
+            .into_iter()
+            .map(|item| {
+                let src_text = map(item.src_text);
+                let token = item.token.map(|token| token.map_content(&mut map));
+                TokenLineItem::new(src_text, token, item.span)
+            })
+            .collect();
+        let ending = ending.map(|item| {
+            let (header, body) = item.src_text;
+            let header = map(header);
+            let body = body
+                .into_iter()
+                .map(|(ln_text, eol)| (map(ln_text), eol))
+                .collect();
+            let token = item.token.map_content(&mut map);
+            TokenLineItem::new((header, body), token, item.span)
+        });
+        TokenLine {
+            ln_text,
+            eol,
+            indent,
+            middle,
+            ending,
+        }
+    }
+}
+
+impl<Content, NewContent> MapContent<Content, NewContent> for LayoutToken<Content> {
+    type Output = LayoutToken<NewContent>;
+    fn map_content<Map>(self, map: Map) -> Self::Output
+    where
+        Map: FnMut(Content) -> NewContent,
+    {
+        match self {
+            LayoutToken::Line(line) => LayoutToken::Line(line.map_content(map)),
+            LayoutToken::Blank(line) => LayoutToken::Blank(line.map_content(map)),
+            LayoutToken::Newline => LayoutToken::Newline,
+            LayoutToken::Indent => LayoutToken::Indent,
+            LayoutToken::Dedent => LayoutToken::Dedent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::Scan;
+    use pretty_assertions::assert_eq;
+    use std::{ops::Range, sync::Arc};
+
+    static TEXT: &str = concat!(
+        "#! /usr/bin/env egg\n",
+        "@@desc Documentation\n",
+        "pub fn main() do # comment\n",
+        "    let a = f\"{b}\" + 0x1F_u32 * 1.5e3 ++ ❤\n",
+        "    let c = '''yaml\n",
+        "        abc: def\n",
+    );
+
+    #[test]
+    fn owned() {
+        let text = TEXT.to_string();
+        let lines: Vec<_> = Scan::new(&text).collect();
+        let expected = format!("{lines:?}");
+        let owned: Vec<TokenLine<String>> = lines
+            .into_iter()
+            .map(|line| line.map_content(str::to_string))
+            .collect();
+        drop(text);
+        assert_eq!(format!("{owned:?}"), expected);
+    }
+
+    #[test]
+    fn shared() {
+        let lines: Vec<_> = Scan::new(TEXT).collect();
+        let shared: Vec<TokenLine<Arc<str>>> = lines
+            .iter()
+            .cloned()
+            .map(|line| line.map_content(Arc::from))
+            .collect();
+        let handle = std::thread::spawn(move || format!("{shared:?}"));
+        assert_eq!(handle.join().unwrap(), format!("{lines:?}"));
+    }
+
+    #[test]
+    fn span_based() {
+        let lines: Vec<_> = Scan::new(TEXT).collect();
+        let range_of = |content: &str| {
+            if content.is_empty() {
+                return 0..0; // empty contents may not point into the text
+            }
+            let start = content.as_ptr() as usize - TEXT.as_ptr() as usize;
+            start..(start + content.len())
+        };
+        let ranges: Vec<TokenLine<Range<usize>>> = lines
+            .iter()
+            .cloned()
+            .map(|line| line.map_content(range_of))
+            .collect();
+        let restored: Vec<_> = ranges
+            .into_iter()
+            .map(|line| line.map_content(|range| &TEXT[range]))
+            .collect();
+        assert_eq!(restored, lines);
+    }
+}
@@ -1,6 +1,6 @@
 pub mod bracket;
 pub mod number;
-mod operator;
+pub mod operator;
 mod punctuation;
 pub mod string;
 mod token;
@@ -14,4 +14,4 @@ pub use punctuation::PunctuationToken;
 pub use string::StringToken;
 pub use token::MiddleToken;
 pub use whitespace::WhitespaceToken;
-pub use word::WordToken;
+pub use word::{IdentifierMode, WordToken};
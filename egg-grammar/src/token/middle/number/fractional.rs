@@ -1,3 +1,7 @@
+mod evaluate;
+
+pub use evaluate::*;
+
 use super::DecimalToken;
 use crate::token::ParseMiddleToken;
 use egg_common_utils::is_number_body;
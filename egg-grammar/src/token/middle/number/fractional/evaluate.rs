@@ -0,0 +1,356 @@
+mod big_uint;
+
+use super::FractionalToken;
+use crate::token::number::{NumberSuffixType, NumberTokenSuffix};
+use big_uint::BigUint;
+use derive_more::{Display, Error};
+
+/// Floating point type of a [`FractionalToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatType {
+    /// 32-bit floating point number.
+    F32,
+    /// 64-bit floating point number.
+    F64,
+}
+
+impl FloatType {
+    /// Infer a floating point type from the suffix of a number.
+    ///
+    /// A number without suffix and a number with the bare `f` suffix are both [`FloatType::F64`].
+    pub fn from_suffix(suffix: Option<&str>) -> Option<Self> {
+        match suffix {
+            None => Some(FloatType::F64),
+            Some(suffix) => NumberSuffixType::from_suffix(suffix)?.float_type(),
+        }
+    }
+}
+
+/// Value of a [`FractionalToken`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatValue {
+    F32(f32),
+    F64(f64),
+}
+
+impl FloatValue {
+    /// Get the type of the value.
+    pub const fn float_type(self) -> FloatType {
+        match self {
+            FloatValue::F32(_) => FloatType::F32,
+            FloatValue::F64(_) => FloatType::F64,
+        }
+    }
+
+    /// Convert the value to [`f64`] without loss.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            FloatValue::F32(value) => value.into(),
+            FloatValue::F64(value) => value,
+        }
+    }
+}
+
+/// Loss of precision when converting a [`FractionalToken`] to a [`FloatValue`].
+///
+/// The warnings are mutually exclusive: overflow and underflow are not reported as inexact.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum FloatWarning {
+    #[display(fmt = "Number is too large, it was rounded to infinity")]
+    Overflow,
+    #[display(fmt = "Number is too small, it was rounded to zero")]
+    Underflow,
+    #[display(fmt = "Number cannot be represented exactly, it was rounded to the nearest value")]
+    Inexact,
+}
+
+/// Successful result of [`FractionalToken::evaluate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatEvaluation {
+    /// The correctly rounded value.
+    pub value: FloatValue,
+    /// Loss of precision (if any).
+    pub warning: Option<FloatWarning>,
+}
+
+/// Part of a [`FractionalToken`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum FractionalPart {
+    #[display(fmt = "integer")]
+    Integer,
+    #[display(fmt = "fraction")]
+    Fraction,
+    #[display(fmt = "exponent")]
+    Exponent,
+}
+
+/// Error when evaluating a [`FractionalToken`].
+///
+/// Offsets are relative to the start of the [part](FractionalPart) of the token.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FloatValueError {
+    #[display(fmt = "Exponent has no digits")]
+    MissingExponentDigits,
+    #[display(fmt = "Underscore at {offset} of the {part} must not be at the start of the {part}")]
+    LeadingUnderscore { part: FractionalPart, offset: usize },
+    #[display(fmt = "Underscore at {offset} of the {part} must not be at the end of the {part}")]
+    TrailingUnderscore { part: FractionalPart, offset: usize },
+    #[display(fmt = "Underscore at {offset} of the {part} must not follow another underscore")]
+    DoubledUnderscore { part: FractionalPart, offset: usize },
+    #[display(fmt = "Suffix is not a floating point type")]
+    InvalidSuffix,
+}
+
+/// Check the placement of underscores in a part of the token, then remove them.
+///
+/// `start` is the offset of the first digit (after the sign of the exponent).
+fn strip_underscores(
+    part: FractionalPart,
+    text: &str,
+    start: usize,
+    allow_trailing: bool,
+) -> Result<String, FloatValueError> {
+    let mut after_underscore = false;
+    for (offset, char) in text.char_indices().skip(start) {
+        if char != '_' {
+            after_underscore = false;
+            continue;
+        }
+        if offset == start {
+            return Err(FloatValueError::LeadingUnderscore { part, offset });
+        }
+        if after_underscore {
+            return Err(FloatValueError::DoubledUnderscore { part, offset });
+        }
+        after_underscore = true;
+    }
+    if after_underscore && !allow_trailing {
+        let offset = text.len() - 1;
+        return Err(FloatValueError::TrailingUnderscore { part, offset });
+    }
+    Ok(text.replace('_', ""))
+}
+
+/// Split a finite [`f64`] into `(mantissa, exponent)` such that the value is `mantissa * 2^exponent`.
+fn decompose(value: f64) -> (u64, i64) {
+    const MANTISSA_BITS: u32 = 52;
+    const EXPONENT_BIAS: i64 = 1023 + MANTISSA_BITS as i64;
+    let bits = value.to_bits();
+    let mantissa = bits & ((1 << MANTISSA_BITS) - 1);
+    let biased_exponent = ((bits >> MANTISSA_BITS) & 0x7FF) as i64;
+    if biased_exponent == 0 {
+        (mantissa, 1 - EXPONENT_BIAS) // subnormal
+    } else {
+        (
+            mantissa | (1 << MANTISSA_BITS),
+            biased_exponent - EXPONENT_BIAS,
+        )
+    }
+}
+
+/// Check if `digits * 10^exponent` equals a finite floating point value.
+fn is_exact(digits: &str, exponent: i64, value: f64) -> bool {
+    let (mantissa, binary_exponent) = decompose(value);
+    let mut decimal = BigUint::from_decimal(digits);
+    let mut binary = BigUint::from(mantissa);
+    // digits * 10^exponent == mantissa * 2^binary_exponent
+    match exponent >= 0 {
+        true => decimal.mul_pow10(exponent.unsigned_abs()),
+        false => binary.mul_pow10(exponent.unsigned_abs()),
+    }
+    match binary_exponent >= 0 {
+        true => binary.shl(binary_exponent.unsigned_abs()),
+        false => decimal.shl(binary_exponent.unsigned_abs()),
+    }
+    decimal == binary
+}
+
+impl<Content> FractionalToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Convert the token to the correctly rounded floating point value of the type named by the suffix.
+    ///
+    /// See [`FloatType::from_suffix`] for the accepted suffixes.
+    pub fn evaluate<SuffixContent>(
+        &self,
+        suffix: Option<&NumberTokenSuffix<SuffixContent>>,
+    ) -> Result<FloatEvaluation, FloatValueError>
+    where
+        SuffixContent: AsRef<str>,
+    {
+        let suffix = suffix.map(|suffix| suffix.0.as_ref());
+        let float_type = FloatType::from_suffix(suffix).ok_or(FloatValueError::InvalidSuffix)?;
+        let has_suffix = suffix.is_some();
+
+        let integer = strip_underscores(
+            FractionalPart::Integer,
+            self.integer.as_ref(),
+            0,
+            false, // either a fraction or an exponent always follows the integer
+        )?;
+
+        let fraction = match &self.fraction {
+            None => String::new(),
+            Some(fraction) => strip_underscores(
+                FractionalPart::Fraction,
+                fraction.as_ref(),
+                0,
+                has_suffix && self.exponent.is_none(),
+            )?,
+        };
+
+        let exponent: i64 = match &self.exponent {
+            None => 0,
+            Some(exponent) => {
+                let exponent = exponent.as_ref();
+                if !exponent.bytes().any(|byte| byte.is_ascii_digit()) {
+                    return Err(FloatValueError::MissingExponentDigits);
+                }
+                let (sign_len, is_negative) = match exponent.as_bytes()[0] {
+                    b'+' => (1, false),
+                    b'-' => (1, true),
+                    _ => (0, false),
+                };
+                let exponent =
+                    strip_underscores(FractionalPart::Exponent, exponent, sign_len, has_suffix)?;
+                // exponents beyond the range of i64 overflow or underflow any float type anyway
+                exponent.parse().unwrap_or(match is_negative {
+                    true => i64::MIN,
+                    false => i64::MAX,
+                })
+            }
+        };
+
+        let digits = format!("{integer}{fraction}");
+        let exponent = exponent.saturating_sub(fraction.len() as i64);
+        let text = format!("{digits}e{exponent}");
+        let value = match float_type {
+            FloatType::F32 => FloatValue::F32(text.parse().expect("text is a valid float")),
+            FloatType::F64 => FloatValue::F64(text.parse().expect("text is a valid float")),
+        };
+
+        let float = value.to_f64();
+        let is_zero = digits.bytes().all(|byte| byte == b'0');
+        let warning = if float.is_infinite() {
+            Some(FloatWarning::Overflow)
+        } else if is_zero {
+            None
+        } else if float == 0.0 {
+            Some(FloatWarning::Underflow)
+        } else if !is_exact(&digits, exponent, float) {
+            Some(FloatWarning::Inexact)
+        } else {
+            None
+        };
+
+        Ok(FloatEvaluation { value, warning })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::{
+        number::{NumberToken, NumberTokenBody},
+        ParseMiddleToken,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn evaluate(input: &str) -> Result<FloatEvaluation, FloatValueError> {
+        let (NumberToken { body, suffix }, "") = NumberToken::parse(input).unwrap() else {
+            panic!("input should be a whole number token");
+        };
+        let NumberTokenBody::Fractional(token) = body else {
+            panic!("input should be a fractional number");
+        };
+        token.evaluate(suffix.as_ref())
+    }
+
+    #[test]
+    fn positive() {
+        use FloatWarning::*;
+
+        macro_rules! case {
+            ($input:literal -> $variant:ident($value:expr), $warning:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                let FloatEvaluation { value, warning } = evaluate($input).unwrap();
+                let FloatValue::$variant(received) = value else {
+                    panic!("expecting {}, received {value:?}", stringify!($variant));
+                };
+                assert_eq!(
+                    (received.to_bits(), warning),
+                    (($value).to_bits(), $warning),
+                );
+            }};
+        }
+
+        case!("0.0" -> F64(0.0f64), None);
+        case!("0.0e999999999999999999999" -> F64(0.0f64), None);
+        case!("1.5" -> F64(1.5f64), None);
+        case!("1.5f" -> F64(1.5f64), None);
+        case!("1.5f64" -> F64(1.5f64), None);
+        case!("1.5f32" -> F32(1.5f32), None);
+        case!("1_000.250_5e-1_0" -> F64(1_000.250_5e-1_0f64), Some(Inexact));
+        case!("1_000.25e1_0" -> F64(1_000.25e10f64), None);
+        case!("1.5_f32" -> F32(1.5f32), None);
+        case!("0.1" -> F64(0.1f64), Some(Inexact));
+        case!("0.1f32" -> F32(0.1f32), Some(Inexact));
+        case!("0.5e-1" -> F64(0.05f64), Some(Inexact));
+        case!("0.125" -> F64(0.125f64), None);
+        case!("2e-3" -> F64(0.002f64), Some(Inexact));
+        case!("1e23" -> F64(1e23f64), Some(Inexact));
+        case!("1e22" -> F64(1e22f64), None);
+        case!("1e+22" -> F64(1e22f64), None);
+        case!("16777217.0f32" -> F32(16777216f32), Some(Inexact));
+        case!("16777217.0" -> F64(16777217f64), None);
+        case!("9007199254740993.0" -> F64(9007199254740992f64), Some(Inexact));
+        case!("4.9406564584124654e-324" -> F64(f64::from_bits(1)), Some(Inexact));
+        case!("2.2250738585072014e-308" -> F64(f64::MIN_POSITIVE), Some(Inexact));
+        case!("1.7976931348623157e308" -> F64(f64::MAX), Some(Inexact));
+        case!("340282346638528859811704183484516925440.0f32" -> F32(f32::MAX), None);
+        case!("1e39f32" -> F32(f32::INFINITY), Some(Overflow));
+        case!("1e309" -> F64(f64::INFINITY), Some(Overflow));
+        case!("1e99999999999999999999" -> F64(f64::INFINITY), Some(Overflow));
+        case!("1e-46f32" -> F32(0.0f32), Some(Underflow));
+        case!("1e-400" -> F64(0.0f64), Some(Underflow));
+        case!("1e-99999999999999999999" -> F64(0.0f64), Some(Underflow));
+    }
+
+    #[test]
+    fn negative() {
+        use FloatValueError::*;
+        use FractionalPart::*;
+
+        macro_rules! case {
+            ($input:literal -> $error:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(evaluate($input), Err($error));
+            }};
+        }
+
+        case!("1e+" -> MissingExponentDigits);
+        case!("1e-" -> MissingExponentDigits);
+        case!("1e_" -> MissingExponentDigits);
+        case!("1e+_" -> MissingExponentDigits);
+        case!("1e_5" -> LeadingUnderscore { part: Exponent, offset: 0 });
+        case!("1e-_5" -> LeadingUnderscore { part: Exponent, offset: 1 });
+        case!("1e5_" -> TrailingUnderscore { part: Exponent, offset: 1 });
+        case!("1e5__f32" -> DoubledUnderscore { part: Exponent, offset: 2 });
+        case!("1_.5" -> TrailingUnderscore { part: Integer, offset: 1 });
+        case!("1__0.5" -> DoubledUnderscore { part: Integer, offset: 2 });
+        case!("1.5_" -> TrailingUnderscore { part: Fraction, offset: 1 });
+        case!("1.5_e3" -> TrailingUnderscore { part: Fraction, offset: 1 });
+        case!("1.5i32" -> InvalidSuffix);
+        case!("1.5f16" -> InvalidSuffix);
+    }
+
+    #[test]
+    fn float_type() {
+        assert_eq!(FloatType::from_suffix(None), Some(FloatType::F64));
+        assert_eq!(FloatType::from_suffix(Some("f")), Some(FloatType::F64));
+        assert_eq!(FloatType::from_suffix(Some("f64")), Some(FloatType::F64));
+        assert_eq!(FloatType::from_suffix(Some("f32")), Some(FloatType::F32));
+        assert_eq!(FloatType::from_suffix(Some("u8")), None);
+    }
+}
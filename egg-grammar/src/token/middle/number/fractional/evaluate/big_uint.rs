@@ -0,0 +1,116 @@
+/// Minimal arbitrary-precision unsigned integer, used to compare decimal and binary values exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BigUint {
+    /// Little-endian limbs without trailing zeros.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Parse a string of decimal digits.
+    pub(super) fn from_decimal(digits: &str) -> Self {
+        let mut number = BigUint { limbs: Vec::new() };
+        for digit in digits.bytes() {
+            debug_assert!(digit.is_ascii_digit());
+            number.mul_add(10, u32::from(digit - b'0'));
+        }
+        number
+    }
+
+    /// Compute `self * factor + addend`.
+    fn mul_add(&mut self, factor: u32, addend: u32) {
+        let mut carry = u64::from(addend);
+        for limb in &mut self.limbs {
+            let product = u64::from(*limb) * u64::from(factor) + carry;
+            *limb = product as u32;
+            carry = product >> u32::BITS;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.normalize();
+    }
+
+    /// Multiply by `10^exponent`.
+    pub(super) fn mul_pow10(&mut self, exponent: u64) {
+        const MAX_POW5_EXPONENT: u64 = 13; // 5^13 is the largest power of 5 that fits in u32
+        let mut remaining = exponent;
+        while remaining > 0 {
+            let step = remaining.min(MAX_POW5_EXPONENT);
+            self.mul_add(5u32.pow(step as u32), 0);
+            remaining -= step;
+        }
+        self.shl(exponent);
+    }
+
+    /// Multiply by `2^bits`.
+    pub(super) fn shl(&mut self, bits: u64) {
+        if self.limbs.is_empty() {
+            return;
+        }
+        let limb_shift = (bits / u64::from(u32::BITS)) as usize;
+        let bit_shift = (bits % u64::from(u32::BITS)) as u32;
+        if bit_shift != 0 {
+            let mut carry = 0;
+            for limb in &mut self.limbs {
+                let shifted = (u64::from(*limb) << bit_shift) | carry;
+                *limb = shifted as u32;
+                carry = shifted >> u32::BITS;
+            }
+            if carry != 0 {
+                self.limbs.push(carry as u32);
+            }
+        }
+        self.limbs
+            .splice(0..0, std::iter::repeat(0).take(limb_shift));
+    }
+
+    /// Remove the trailing zero limbs.
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut number = BigUint {
+            limbs: vec![value as u32, (value >> u32::BITS) as u32],
+        };
+        number.normalize();
+        number
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(BigUint::from_decimal(""), BigUint::from(0));
+        assert_eq!(BigUint::from_decimal("000"), BigUint::from(0));
+        assert_eq!(
+            BigUint::from_decimal("18446744073709551615"),
+            BigUint::from(u64::MAX),
+        );
+
+        let mut number = BigUint::from(3);
+        number.mul_pow10(19);
+        assert_eq!(number, BigUint::from_decimal("30000000000000000000"));
+
+        let mut number = BigUint::from(u64::MAX);
+        number.shl(70);
+        let mut expected = BigUint::from_decimal("18446744073709551615");
+        expected.mul_add(1 << 30, 0);
+        expected.mul_add(1 << 30, 0);
+        expected.mul_add(1 << 10, 0);
+        assert_eq!(number, expected);
+
+        let mut zero = BigUint::from(0);
+        zero.shl(100);
+        zero.mul_pow10(100);
+        assert_eq!(zero, BigUint::from(0));
+    }
+}
@@ -1,10 +1,12 @@
 mod binary;
 mod decimal;
+mod evaluate;
 mod hexadecimal;
 mod octal;
 
 pub use binary::*;
 pub use decimal::*;
+pub use evaluate::*;
 pub use hexadecimal::*;
 pub use octal::*;
 
@@ -0,0 +1,205 @@
+use super::IntegerToken;
+use crate::token::number::{NumberSuffixType, NumberTokenSuffix};
+use derive_more::{Display, Error};
+
+/// Error when evaluating an [`IntegerToken`].
+///
+/// Offsets are relative to the start of the digits, not including the prefix (`0b`, `0o`, `0x`).
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum IntegerValueError {
+    #[display(fmt = "Number has no digits")]
+    NoDigits,
+    #[display(fmt = "Digit {digit:?} at {offset} is not valid in base {radix}")]
+    InvalidDigit {
+        offset: usize,
+        digit: char,
+        radix: u32,
+    },
+    #[display(fmt = "Underscore at {offset} must not be at the start of the number")]
+    LeadingUnderscore { offset: usize },
+    #[display(fmt = "Underscore at {offset} must not be at the end of the number")]
+    TrailingUnderscore { offset: usize },
+    #[display(fmt = "Underscore at {offset} must not follow another underscore")]
+    DoubledUnderscore { offset: usize },
+    #[display(fmt = "Digit at {offset} makes the number too large for 128 bits")]
+    TooLarge { offset: usize },
+    #[display(fmt = "Number {value} is out of the range of {ty}")]
+    OutOfRange { value: u128, ty: NumberSuffixType },
+}
+
+impl<Content> IntegerToken<Content> {
+    /// Get the radix of the number.
+    pub const fn radix(&self) -> u32 {
+        match self {
+            IntegerToken::Decimal(_) => 10,
+            IntegerToken::Binary(_) => 2,
+            IntegerToken::Octal(_) => 8,
+            IntegerToken::Hexadecimal(_) => 16,
+        }
+    }
+
+    /// Get the digits of the number, not including the prefix.
+    pub const fn digits(&self) -> &'_ Content {
+        match self {
+            IntegerToken::Decimal(token) => &token.0,
+            IntegerToken::Binary(token) => &token.0,
+            IntegerToken::Octal(token) => &token.0,
+            IntegerToken::Hexadecimal(token) => &token.0,
+        }
+    }
+}
+
+impl<Content> IntegerToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Evaluate the value of the number.
+    ///
+    /// * Every digit must be valid in the [radix](IntegerToken::radix) of the number.
+    /// * Underscores must be between digits, a single underscore may also separate
+    ///   the digits from a suffix (e.g. `123_456_i32`).
+    /// * If the suffix names an integer type, the value must fit in the type.
+    ///   Negative numbers are composed of `-` and a number, so only the positive range is checked.
+    pub fn evaluate<SuffixContent>(
+        &self,
+        suffix: Option<&NumberTokenSuffix<SuffixContent>>,
+    ) -> Result<u128, IntegerValueError>
+    where
+        SuffixContent: AsRef<str>,
+    {
+        let digits = self.digits().as_ref();
+        let radix = self.radix();
+        if digits.is_empty() {
+            return Err(IntegerValueError::NoDigits);
+        }
+
+        let mut value: u128 = 0;
+        let mut after_underscore = false;
+        for (offset, char) in digits.char_indices() {
+            if char == '_' {
+                if offset == 0 {
+                    return Err(IntegerValueError::LeadingUnderscore { offset });
+                }
+                if after_underscore {
+                    return Err(IntegerValueError::DoubledUnderscore { offset });
+                }
+                after_underscore = true;
+                continue;
+            }
+            after_underscore = false;
+            let digit = char
+                .to_digit(radix)
+                .ok_or(IntegerValueError::InvalidDigit {
+                    offset,
+                    digit: char,
+                    radix,
+                })?;
+            value = value
+                .checked_mul(radix.into())
+                .and_then(|value| value.checked_add(digit.into()))
+                .ok_or(IntegerValueError::TooLarge { offset })?;
+        }
+
+        if after_underscore && suffix.is_none() {
+            let offset = digits.len() - 1;
+            return Err(IntegerValueError::TrailingUnderscore { offset });
+        }
+
+        let ty = suffix.and_then(|suffix| NumberSuffixType::from_suffix(suffix.0.as_ref()));
+        if let Some(ty) = ty {
+            if ty.integer_max().map_or(false, |max| value > max) {
+                return Err(IntegerValueError::OutOfRange { value, ty });
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::{
+        number::{NumberToken, NumberTokenBody},
+        ParseMiddleToken,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn evaluate(input: &str) -> Result<u128, IntegerValueError> {
+        let (NumberToken { body, suffix }, "") = NumberToken::parse(input).unwrap() else {
+            panic!("input should be a whole number token");
+        };
+        let NumberTokenBody::Integer(token) = body else {
+            panic!("input should be an integer");
+        };
+        token.evaluate(suffix.as_ref())
+    }
+
+    #[test]
+    fn positive() {
+        macro_rules! case {
+            ($input:literal -> $value:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(evaluate($input), Ok($value));
+            }};
+        }
+
+        case!("0" -> 0);
+        case!("123" -> 123);
+        case!("123_456_789" -> 123_456_789);
+        case!("123_456_i32" -> 123_456);
+        case!("0b1001_1100" -> 0b1001_1100);
+        case!("0o1234567" -> 0o1234567);
+        case!("0x12_3A_bc_u64" -> 0x12_3ABC);
+        case!("255u8" -> 255);
+        case!("127i8" -> 127);
+        case!("0xFFFF_FFFF_u32" -> 0xFFFF_FFFF);
+        case!("340282366920938463463374607431768211455" -> u128::MAX);
+        case!("340282366920938463463374607431768211455u128" -> u128::MAX);
+        case!("170141183460469231731687303715884105727i128" -> i128::MAX as u128);
+        case!("123suffix" -> 123);
+    }
+
+    #[test]
+    fn negative() {
+        use IntegerValueError::*;
+
+        macro_rules! case {
+            ($input:literal -> $error:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(evaluate($input), Err($error));
+            }};
+        }
+
+        case!("0xg" -> NoDigits);
+        case!("0b102" -> InvalidDigit { offset: 2, digit: '2', radix: 2 });
+        case!("0o1289" -> InvalidDigit { offset: 2, digit: '8', radix: 8 });
+        case!("0x_12" -> LeadingUnderscore { offset: 0 });
+        case!("0b_1_u8" -> LeadingUnderscore { offset: 0 });
+        case!("123_" -> TrailingUnderscore { offset: 3 });
+        case!("0x12_" -> TrailingUnderscore { offset: 2 });
+        case!("1__000" -> DoubledUnderscore { offset: 2 });
+        case!("1000__u32" -> DoubledUnderscore { offset: 5 });
+        case!("340282366920938463463374607431768211456" -> TooLarge { offset: 38 });
+        case!("0x1_0000_0000_0000_0000_0000_0000_0000_0000" -> TooLarge { offset: 40 });
+        case!("256u8" -> OutOfRange { value: 256, ty: NumberSuffixType::U8 });
+        case!("128i8" -> OutOfRange { value: 128, ty: NumberSuffixType::I8 });
+        case!("0x1_0000_0000u32" -> OutOfRange { value: 0x1_0000_0000, ty: NumberSuffixType::U32 });
+    }
+
+    #[test]
+    fn radix() {
+        macro_rules! case {
+            ($input:literal -> $radix:literal, $digits:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let (token, _) = IntegerToken::parse($input).unwrap();
+                assert_eq!((token.radix(), *token.digits()), ($radix, $digits));
+            }};
+        }
+
+        case!("123" -> 10, "123");
+        case!("0b101" -> 2, "101");
+        case!("0o123" -> 8, "123");
+        case!("0x1aF" -> 16, "1aF");
+    }
+}
@@ -1,3 +1,7 @@
+mod classify;
+
+pub use classify::*;
+
 use crate::token::ParseMiddleToken;
 use derive_more::{AsRef, Deref};
 
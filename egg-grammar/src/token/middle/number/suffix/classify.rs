@@ -0,0 +1,275 @@
+use super::NumberTokenSuffix;
+use crate::{
+    keyword::Keyword,
+    token::number::{FloatType, HexadecimalToken, IntegerToken, NumberToken, NumberTokenBody},
+};
+use derive_more::{Display, Error};
+use strum::{AsRefStr, EnumString, IntoStaticStr};
+
+/// Suffix that is an alias of `f64`.
+const FLOAT_SUFFIX_ALIAS: &str = "f";
+
+/// Primitive numeric type named by a [`NumberTokenSuffix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)] // essential std traits
+#[derive(AsRefStr, strum::Display, EnumString, IntoStaticStr)] // essential strum traits
+#[strum(serialize_all = "lowercase")]
+pub enum NumberSuffixType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+}
+
+impl NumberSuffixType {
+    /// Get the type named by a keyword.
+    pub const fn from_keyword(keyword: Keyword) -> Option<Self> {
+        Some(match keyword {
+            Keyword::U8 => NumberSuffixType::U8,
+            Keyword::U16 => NumberSuffixType::U16,
+            Keyword::U32 => NumberSuffixType::U32,
+            Keyword::U64 => NumberSuffixType::U64,
+            Keyword::U128 => NumberSuffixType::U128,
+            Keyword::I8 => NumberSuffixType::I8,
+            Keyword::I16 => NumberSuffixType::I16,
+            Keyword::I32 => NumberSuffixType::I32,
+            Keyword::I64 => NumberSuffixType::I64,
+            Keyword::I128 => NumberSuffixType::I128,
+            Keyword::F32 => NumberSuffixType::F32,
+            Keyword::F64 => NumberSuffixType::F64,
+            _ => return None,
+        })
+    }
+
+    /// Get the keyword that names the type.
+    pub const fn to_keyword(self) -> Keyword {
+        match self {
+            NumberSuffixType::U8 => Keyword::U8,
+            NumberSuffixType::U16 => Keyword::U16,
+            NumberSuffixType::U32 => Keyword::U32,
+            NumberSuffixType::U64 => Keyword::U64,
+            NumberSuffixType::U128 => Keyword::U128,
+            NumberSuffixType::I8 => Keyword::I8,
+            NumberSuffixType::I16 => Keyword::I16,
+            NumberSuffixType::I32 => Keyword::I32,
+            NumberSuffixType::I64 => Keyword::I64,
+            NumberSuffixType::I128 => Keyword::I128,
+            NumberSuffixType::F32 => Keyword::F32,
+            NumberSuffixType::F64 => Keyword::F64,
+        }
+    }
+
+    /// Get the type named by the text of a suffix.
+    ///
+    /// The bare `f` suffix is an alias of [`NumberSuffixType::F64`].
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        if suffix == FLOAT_SUFFIX_ALIAS {
+            return Some(NumberSuffixType::F64);
+        }
+        Keyword::try_from(suffix)
+            .ok()
+            .and_then(NumberSuffixType::from_keyword)
+    }
+
+    /// Check if the type is a floating point type.
+    pub const fn is_float(self) -> bool {
+        matches!(self, NumberSuffixType::F32 | NumberSuffixType::F64)
+    }
+
+    /// Check if the type is an integer type.
+    pub const fn is_integer(self) -> bool {
+        !self.is_float()
+    }
+
+    /// Get the floating point type (if the type is one).
+    pub const fn float_type(self) -> Option<FloatType> {
+        match self {
+            NumberSuffixType::F32 => Some(FloatType::F32),
+            NumberSuffixType::F64 => Some(FloatType::F64),
+            _ => None,
+        }
+    }
+
+    /// Get the maximum value of an integer type.
+    ///
+    /// Return `None` if the type isn't an integer type.
+    pub const fn integer_max(self) -> Option<u128> {
+        Some(match self {
+            NumberSuffixType::U8 => u8::MAX as u128,
+            NumberSuffixType::U16 => u16::MAX as u128,
+            NumberSuffixType::U32 => u32::MAX as u128,
+            NumberSuffixType::U64 => u64::MAX as u128,
+            NumberSuffixType::U128 => u128::MAX,
+            NumberSuffixType::I8 => i8::MAX as u128,
+            NumberSuffixType::I16 => i16::MAX as u128,
+            NumberSuffixType::I32 => i32::MAX as u128,
+            NumberSuffixType::I64 => i64::MAX as u128,
+            NumberSuffixType::I128 => i128::MAX as u128,
+            NumberSuffixType::F32 | NumberSuffixType::F64 => return None,
+        })
+    }
+}
+
+impl From<NumberSuffixType> for Keyword {
+    fn from(ty: NumberSuffixType) -> Self {
+        ty.to_keyword()
+    }
+}
+
+/// Error when failing to [convert](TryFrom) a [`Keyword`] to a [`NumberSuffixType`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+#[display(fmt = "{_0} is not a numeric type")]
+pub struct NotNumericType(#[error(not(source))] Keyword);
+
+impl TryFrom<Keyword> for NumberSuffixType {
+    type Error = NotNumericType;
+    fn try_from(keyword: Keyword) -> Result<Self, Self::Error> {
+        NumberSuffixType::from_keyword(keyword).ok_or(NotNumericType(keyword))
+    }
+}
+
+/// Suffix that names a numeric type that the body of the number cannot have.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidSuffix {
+    #[display(fmt = "Binary, octal, and hexadecimal numbers cannot be {_0}")]
+    FloatOnNonDecimalInteger(#[error(not(source))] NumberSuffixType),
+    #[display(fmt = "Fractional numbers cannot be {_0}")]
+    IntegerOnFractional(#[error(not(source))] NumberSuffixType),
+    #[display(
+        fmt = "Suffix _{_0} was parsed as hexadecimal digits, hexadecimal numbers cannot be {_0}"
+    )]
+    SwallowedByHexadecimal(#[error(not(source))] NumberSuffixType),
+}
+
+/// Result of [`NumberToken::classify_suffix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuffixClass<Content> {
+    /// The number has no suffix.
+    None,
+    /// The suffix names a numeric type that the number can have.
+    Known(NumberSuffixType),
+    /// The suffix doesn't name any numeric type.
+    Unknown(Content),
+    /// The suffix names a numeric type that the number cannot have.
+    Invalid(InvalidSuffix),
+}
+
+/// Detect a float suffix that was swallowed by the digits of a hexadecimal number (e.g. `0x123ABC_f32`).
+///
+/// Only suffixes separated by an underscore are detected, `0x1f32` is a valid hexadecimal number.
+fn swallowed_float_suffix(digits: &str) -> Option<NumberSuffixType> {
+    let (digits, suffix) = digits.rsplit_once('_')?;
+    if digits.is_empty() {
+        return None;
+    }
+    NumberSuffixType::from_suffix(suffix).filter(|ty| ty.is_float() && suffix != FLOAT_SUFFIX_ALIAS)
+}
+
+impl<Content> NumberToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Classify the suffix of the number.
+    pub fn classify_suffix(&self) -> SuffixClass<&'_ Content> {
+        if let (
+            None,
+            NumberTokenBody::Integer(IntegerToken::Hexadecimal(HexadecimalToken(digits))),
+        ) = (&self.suffix, &self.body)
+        {
+            if let Some(ty) = swallowed_float_suffix(digits.as_ref()) {
+                return SuffixClass::Invalid(InvalidSuffix::SwallowedByHexadecimal(ty));
+            }
+        }
+
+        let Some(NumberTokenSuffix(suffix)) = &self.suffix else {
+            return SuffixClass::None;
+        };
+        let Some(ty) = NumberSuffixType::from_suffix(suffix.as_ref()) else {
+            return SuffixClass::Unknown(suffix);
+        };
+
+        match (&self.body, ty.is_float()) {
+            (NumberTokenBody::Integer(IntegerToken::Decimal(_)), _) => SuffixClass::Known(ty),
+            (NumberTokenBody::Integer(_), true) => {
+                SuffixClass::Invalid(InvalidSuffix::FloatOnNonDecimalInteger(ty))
+            }
+            (NumberTokenBody::Fractional(_), false) => {
+                SuffixClass::Invalid(InvalidSuffix::IntegerOnFractional(ty))
+            }
+            _ => SuffixClass::Known(ty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::ParseMiddleToken;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn classify_suffix() {
+        use InvalidSuffix::*;
+        use NumberSuffixType::*;
+        use SuffixClass::{Invalid, Known, Unknown};
+
+        macro_rules! case {
+            ($input:literal -> $class:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                let (token, rest) = NumberToken::parse($input).unwrap();
+                assert_eq!(rest, "");
+                assert_eq!(token.classify_suffix(), $class);
+            }};
+        }
+
+        case!("123" -> SuffixClass::None);
+        case!("123i32" -> Known(I32));
+        case!("123_456_u8" -> Known(U8));
+        case!("123f32" -> Known(F32));
+        case!("123f" -> Known(F64));
+        case!("0x123ABCi32" -> Known(I32));
+        case!("0x123ABC_i32" -> Known(I32));
+        case!("0b1010u128" -> Known(U128));
+        case!("1.5f64" -> Known(F64));
+        case!("1.5f" -> Known(F64));
+        case!("1e10f32" -> Known(F32));
+        case!("12banana" -> Unknown(&"banana"));
+        case!("1.5i" -> Unknown(&"i"));
+        case!("123i256" -> Unknown(&"i256"));
+        case!("123I32" -> Unknown(&"I32"));
+        case!("0b101f32" -> Invalid(FloatOnNonDecimalInteger(F32)));
+        case!("0o17f64" -> Invalid(FloatOnNonDecimalInteger(F64)));
+        case!("1.5i32" -> Invalid(IntegerOnFractional(I32)));
+        case!("1e3u8" -> Invalid(IntegerOnFractional(U8)));
+        case!("0x123ABC_f32" -> Invalid(SwallowedByHexadecimal(F32)));
+        case!("0x123ABC_f64" -> Invalid(SwallowedByHexadecimal(F64)));
+        case!("0x123ABCf32" -> SuffixClass::None);
+        case!("0x123ABC_f" -> SuffixClass::None);
+        case!("0x_f32" -> SuffixClass::None);
+    }
+
+    #[test]
+    fn keyword() {
+        use strum::IntoEnumIterator;
+        for keyword in Keyword::iter() {
+            eprintln!("TEST: {keyword:?}");
+            let Ok(ty) = NumberSuffixType::try_from(keyword) else {
+                continue;
+            };
+            assert_eq!(Keyword::from(ty), keyword);
+            assert_eq!(ty.as_ref(), keyword.as_ref());
+            assert_eq!(NumberSuffixType::from_suffix(keyword.as_ref()), Some(ty));
+        }
+        assert_eq!(
+            NumberSuffixType::try_from(Keyword::Bool),
+            Err(NotNumericType(Keyword::Bool)),
+        );
+    }
+}
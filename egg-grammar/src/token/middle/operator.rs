@@ -1,3 +1,9 @@
+mod split;
+mod table;
+
+pub use split::*;
+pub use table::*;
+
 use crate::token::ParseMiddleToken;
 use egg_common_utils::{char_matcher, parse_hb_ascii};
 
@@ -10,7 +16,13 @@ char_matcher!(is_operator_body => '!' | '#'..='&' | '*'..='+' | '-'..='/' | ':'
 
 impl<'a> ParseMiddleToken<&'a str> for OperatorToken<&'a str> {
     fn parse(input: &'a str) -> Option<(Self, &'a str)> {
-        parse_hb_ascii(OperatorToken, input, is_operator_head, is_operator_body)
+        let (token, rest) =
+            parse_hb_ascii(OperatorToken, input, is_operator_head, is_operator_body)?;
+        // a trailing documentation (`@@`) is not a part of the operator before it
+        match token.0.find("@@") {
+            Some(0) | None => Some((token, rest)),
+            Some(end) => Some((OperatorToken(&input[..end]), &input[end..])),
+        }
     }
 }
 
@@ -92,6 +104,8 @@ mod test {
         case!(r#"++"abc"# -> "++", r#""abc"#);
         case!("$interpolation" -> "$", "interpolation");
         case!("@attribute" -> "@", "attribute");
+        case!("+@@desc" -> "+", "@@desc");
+        case!("->@@!desc" -> "->", "@@!desc");
     }
 
     #[test]
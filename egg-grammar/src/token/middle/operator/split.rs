@@ -0,0 +1,106 @@
+use super::{Operator, OperatorToken};
+use derive_more::{Display, Error};
+use strum::IntoEnumIterator;
+
+/// Known operator and its byte offset in the [`OperatorToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitOperator {
+    /// Byte offset of the operator relative to the start of the token.
+    pub offset: usize,
+    /// The operator.
+    pub operator: Operator,
+}
+
+/// Error when an [`OperatorToken`] cannot be split into known operators.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+#[display(fmt = "No known operator starts at {offset}")]
+pub struct UnknownOperator {
+    /// Byte offset of the first character that doesn't start a known operator.
+    pub offset: usize,
+}
+
+/// Find the longest known operator at the start of the input.
+fn longest_operator(input: &str) -> Option<Operator> {
+    Operator::iter()
+        .filter(|operator| input.starts_with(operator.as_str()))
+        .max_by_key(|operator| operator.as_str().len())
+}
+
+impl<Content> OperatorToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Split the token into known operators.
+    ///
+    /// The longest known operator is always taken first, so `+=-` is split into `+=` and `-`.
+    pub fn split(&self) -> Result<Vec<SplitOperator>, UnknownOperator> {
+        let text = self.0.as_ref();
+        let mut rest = text;
+        let mut operators = Vec::new();
+        while !rest.is_empty() {
+            let offset = text.len() - rest.len();
+            let operator = longest_operator(rest).ok_or(UnknownOperator { offset })?;
+            operators.push(SplitOperator { offset, operator });
+            rest = &rest[operator.as_str().len()..];
+        }
+        Ok(operators)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::ParseMiddleToken;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        use Operator::*;
+
+        macro_rules! case {
+            ($input:literal -> [$($offset:literal $operator:ident),* $(,)?]) => {{
+                eprintln!("TEST: {:?}", $input);
+                let (token, _) = OperatorToken::parse($input).unwrap();
+                let expected = vec![$(SplitOperator {
+                    offset: $offset,
+                    operator: $operator,
+                }),*];
+                assert_eq!(token.split(), Ok(expected));
+            }};
+        }
+
+        case!("+123" -> [0 Add]);
+        case!("+=123" -> [0 AddAssign]);
+        case!("**=a" -> [0 PowAssign]);
+        case!("***a" -> [0 Pow, 2 Mul]);
+        case!("=-1" -> [0 Assign, 1 Sub]);
+        case!("+=-1" -> [0 AddAssign, 2 Sub]);
+        case!("!=a" -> [0 Ne]);
+        case!("!!a" -> [0 Not, 1 Not]);
+        case!("->u32" -> [0 Arrow]);
+        case!("::abc" -> [0 Path]);
+        case!("..=10" -> [0 RangeInclusive]);
+        case!("...a" -> [0 Range, 2 Dot]);
+        case!("<<=1" -> [0 ShlAssign]);
+        case!("@!cli" -> [0 AtBang]);
+        case!("@attribute" -> [0 At]);
+        case!("$interpolation" -> [0 Dollar]);
+        case!("|?" -> [0 Pipe, 1 Question]);
+    }
+
+    #[test]
+    fn negative() {
+        macro_rules! case {
+            ($input:literal -> $offset:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let (token, _) = OperatorToken::parse($input).unwrap();
+                assert_eq!(token.split(), Err(UnknownOperator { offset: $offset }));
+            }};
+        }
+
+        case!("+#abc" -> 1);
+        case!("\\abc" -> 0);
+        case!("=\\abc" -> 1);
+        case!("+=#" -> 2);
+    }
+}
@@ -0,0 +1,255 @@
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, IntoStaticStr};
+
+/// Known operator.
+///
+/// The string form of each variant is its symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)] // essential std traits
+#[derive(AsRefStr, Display, EnumCount, EnumIter, EnumString, IntoStaticStr)] // essential strum traits
+pub enum Operator {
+    /* Arithmetic operators */
+    #[strum(serialize = "+")]
+    Add,
+    #[strum(serialize = "-")]
+    Sub,
+    #[strum(serialize = "*")]
+    Mul,
+    #[strum(serialize = "/")]
+    Div,
+    #[strum(serialize = "%")]
+    Rem,
+    #[strum(serialize = "**")]
+    Pow,
+
+    /* Comparison operators */
+    #[strum(serialize = "==")]
+    Eq,
+    #[strum(serialize = "!=")]
+    Ne,
+    #[strum(serialize = "<")]
+    Lt,
+    #[strum(serialize = "<=")]
+    Le,
+    #[strum(serialize = ">")]
+    Gt,
+    #[strum(serialize = ">=")]
+    Ge,
+
+    /* Bitwise and logical operators */
+    #[strum(serialize = "!")]
+    Not,
+    #[strum(serialize = "~")]
+    BitNot,
+    #[strum(serialize = "&")]
+    BitAnd,
+    #[strum(serialize = "^")]
+    BitXor,
+    #[strum(serialize = "<<")]
+    Shl,
+    #[strum(serialize = ">>")]
+    Shr,
+
+    /* Assignment operators */
+    #[strum(serialize = "=")]
+    Assign,
+    #[strum(serialize = "+=")]
+    AddAssign,
+    #[strum(serialize = "-=")]
+    SubAssign,
+    #[strum(serialize = "*=")]
+    MulAssign,
+    #[strum(serialize = "/=")]
+    DivAssign,
+    #[strum(serialize = "%=")]
+    RemAssign,
+    #[strum(serialize = "**=")]
+    PowAssign,
+    #[strum(serialize = "&=")]
+    BitAndAssign,
+    #[strum(serialize = "^=")]
+    BitXorAssign,
+    #[strum(serialize = "<<=")]
+    ShlAssign,
+    #[strum(serialize = ">>=")]
+    ShrAssign,
+
+    /* Range operators */
+    #[strum(serialize = "..")]
+    Range,
+    #[strum(serialize = "..=")]
+    RangeInclusive,
+
+    /* Access operators */
+    #[strum(serialize = "::")]
+    Path,
+    #[strum(serialize = ".")]
+    Dot,
+
+    /* Structural operators */
+    #[strum(serialize = ":")]
+    Colon,
+    #[strum(serialize = "->")]
+    Arrow,
+    #[strum(serialize = "|")]
+    Pipe,
+    #[strum(serialize = "?")]
+    Question,
+    #[strum(serialize = "@")]
+    At,
+    #[strum(serialize = "@!")]
+    AtBang,
+    #[strum(serialize = "$")]
+    Dollar,
+}
+
+/// Category of an [`Operator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorCategory {
+    /// `+`, `-`, `*`, `/`, `%`, `**`.
+    Arithmetic,
+    /// `==`, `!=`, `<`, `<=`, `>`, `>=`.
+    Comparison,
+    /// `!`, `~`, `&`, `^`, `<<`, `>>`.
+    Bitwise,
+    /// `=`, `+=`, `-=`, etc.
+    Assignment,
+    /// `..`, `..=`.
+    Range,
+    /// Path separator (`::`) and dot notation (`.`).
+    Access,
+    /// `:`, `->`, `|`, `?`, `@`, `@!`, `$`.
+    Structural,
+}
+
+/// Associativity of an infix [`Operator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a op b op c` is `(a op b) op c`.
+    Left,
+    /// `a op b op c` is `a op (b op c)`.
+    Right,
+    /// `a op b op c` is not allowed.
+    None,
+}
+
+/// How an infix [`Operator`] binds its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InfixBinding {
+    /// Operators with higher precedence bind tighter.
+    pub precedence: u8,
+    /// How operators of the same precedence are grouped.
+    pub associativity: Associativity,
+}
+
+/// Precedence of prefix operators (`-a`, `!a`, `~a`).
+///
+/// Prefix operators bind tighter than `*` but looser than `**`, so `-a ** b` is `-(a ** b)`.
+pub const PREFIX_PRECEDENCE: u8 = 10;
+
+impl Operator {
+    /// Get the symbol of the operator.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Get the category of the operator.
+    pub const fn category(self) -> OperatorCategory {
+        use Operator::*;
+        match self {
+            Add | Sub | Mul | Div | Rem | Pow => OperatorCategory::Arithmetic,
+            Eq | Ne | Lt | Le | Gt | Ge => OperatorCategory::Comparison,
+            Not | BitNot | BitAnd | BitXor | Shl | Shr => OperatorCategory::Bitwise,
+            Assign | AddAssign | SubAssign | MulAssign | DivAssign | RemAssign | PowAssign
+            | BitAndAssign | BitXorAssign | ShlAssign | ShrAssign => OperatorCategory::Assignment,
+            Range | RangeInclusive => OperatorCategory::Range,
+            Path | Dot => OperatorCategory::Access,
+            Colon | Arrow | Pipe | Question | At | AtBang | Dollar => OperatorCategory::Structural,
+        }
+    }
+
+    /// Get the binding of the operator when it is placed between 2 operands.
+    ///
+    /// Return `None` if the operator cannot be used as an infix operator of an expression.
+    pub const fn infix(self) -> Option<InfixBinding> {
+        use Operator::*;
+        let (precedence, associativity) = match self {
+            Assign | AddAssign | SubAssign | MulAssign | DivAssign | RemAssign | PowAssign
+            | BitAndAssign | BitXorAssign | ShlAssign | ShrAssign => (1, Associativity::Right),
+            Pipe => (2, Associativity::Left),
+            Range | RangeInclusive => (3, Associativity::None),
+            Eq | Ne | Lt | Le | Gt | Ge => (4, Associativity::None),
+            BitXor => (5, Associativity::Left),
+            BitAnd => (6, Associativity::Left),
+            Shl | Shr => (7, Associativity::Left),
+            Add | Sub => (8, Associativity::Left),
+            Mul | Div | Rem => (9, Associativity::Left),
+            Pow => (11, Associativity::Right),
+            Dot => (12, Associativity::Left),
+            Path => (13, Associativity::Left),
+            Not | BitNot | Colon | Arrow | Question | At | AtBang | Dollar => return None,
+        };
+        Some(InfixBinding {
+            precedence,
+            associativity,
+        })
+    }
+
+    /// Check if the operator can be placed before an operand.
+    ///
+    /// All prefix operators have the same [precedence](PREFIX_PRECEDENCE).
+    pub const fn is_prefix(self) -> bool {
+        matches!(self, Operator::Sub | Operator::Not | Operator::BitNot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn symbols() {
+        for operator in Operator::iter() {
+            eprintln!("TEST: {operator:?}");
+            assert_eq!(operator.as_str().parse(), Ok(operator));
+            assert_eq!(operator.to_string(), operator.as_str());
+        }
+        assert!("#".parse::<Operator>().is_err());
+        assert!("+#".parse::<Operator>().is_err());
+    }
+
+    #[test]
+    fn precedence() {
+        let precedence = |operator: Operator| operator.infix().unwrap().precedence;
+        assert!(precedence(Operator::Mul) > precedence(Operator::Add));
+        assert!(precedence(Operator::Add) > precedence(Operator::Eq));
+        assert!(precedence(Operator::Eq) > precedence(Operator::Pipe));
+        assert!(precedence(Operator::Pipe) > precedence(Operator::Assign));
+        assert!(precedence(Operator::Pow) > PREFIX_PRECEDENCE);
+        assert!(PREFIX_PRECEDENCE > precedence(Operator::Mul));
+        assert_eq!(
+            Operator::Assign.infix().unwrap().associativity,
+            Associativity::Right
+        );
+        assert_eq!(
+            Operator::Sub.infix().unwrap().associativity,
+            Associativity::Left
+        );
+        assert_eq!(
+            Operator::Lt.infix().unwrap().associativity,
+            Associativity::None
+        );
+        assert_eq!(Operator::At.infix(), None);
+        assert!(Operator::Sub.is_prefix());
+        assert!(!Operator::Add.is_prefix());
+    }
+
+    #[test]
+    fn category() {
+        assert_eq!(Operator::Pow.category(), OperatorCategory::Arithmetic);
+        assert_eq!(Operator::Ge.category(), OperatorCategory::Comparison);
+        assert_eq!(Operator::ShlAssign.category(), OperatorCategory::Assignment);
+        assert_eq!(Operator::Path.category(), OperatorCategory::Access);
+        assert_eq!(Operator::AtBang.category(), OperatorCategory::Structural);
+    }
+}
@@ -1,7 +1,13 @@
 mod error;
+mod escape;
+mod interpolation;
+mod prefix;
 mod quote;
 
 pub use error::*;
+pub use escape::*;
+pub use interpolation::*;
+pub use prefix::*;
 pub use quote::*;
 
 use crate::token::ParseMiddleToken;
@@ -0,0 +1,245 @@
+use super::StringToken;
+use derive_more::{Display, Error};
+use split_first_char::split_first_char;
+
+/// Error when cooking the escape sequences of a [`StringToken`].
+///
+/// Every variant carries the byte offset of the backslash that started
+/// the malformed escape sequence, relative to the start of the string body.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum EscapeError {
+    #[display(fmt = "Backslash at {offset} is not followed by any character")]
+    IncompleteEscape { offset: usize },
+    #[display(fmt = "Unknown escape sequence \\{char} at {offset}")]
+    UnknownEscape { offset: usize, char: char },
+    #[display(fmt = "Hex escape at {offset} must have exactly 2 hexadecimal digits")]
+    InvalidHexEscape { offset: usize },
+    #[display(fmt = "Hex escape at {offset} is out of range ({value:#04X} > 0x7F)")]
+    HexEscapeOutOfRange { offset: usize, value: u8 },
+    #[display(fmt = "Unicode escape at {offset} must start with an opening curly bracket")]
+    UnicodeEscapeWithoutBrace { offset: usize },
+    #[display(fmt = "Unicode escape at {offset} is not terminated with a closing curly bracket")]
+    UnterminatedUnicodeEscape { offset: usize },
+    #[display(fmt = "Unicode escape at {offset} has no digits")]
+    EmptyUnicodeEscape { offset: usize },
+    #[display(fmt = "Unicode escape at {offset} has a non-hexadecimal character {char:?}")]
+    InvalidUnicodeEscapeDigit { offset: usize, char: char },
+    #[display(fmt = "Unicode escape at {offset} has more than 6 hexadecimal digits")]
+    UnicodeEscapeTooLong { offset: usize },
+    #[display(fmt = "Unicode escape at {offset} is not a valid code point ({value:#X})")]
+    InvalidCodePoint { offset: usize, value: u32 },
+}
+
+impl EscapeError {
+    /// Byte offset of the backslash that started the malformed escape sequence.
+    pub const fn offset(&self) -> usize {
+        match self {
+            EscapeError::IncompleteEscape { offset }
+            | EscapeError::UnknownEscape { offset, .. }
+            | EscapeError::InvalidHexEscape { offset }
+            | EscapeError::HexEscapeOutOfRange { offset, .. }
+            | EscapeError::UnicodeEscapeWithoutBrace { offset }
+            | EscapeError::UnterminatedUnicodeEscape { offset }
+            | EscapeError::EmptyUnicodeEscape { offset }
+            | EscapeError::InvalidUnicodeEscapeDigit { offset, .. }
+            | EscapeError::UnicodeEscapeTooLong { offset }
+            | EscapeError::InvalidCodePoint { offset, .. } => *offset,
+        }
+    }
+
+    /// Move the offset of the error forward.
+    ///
+    /// This is useful when the cooked text is only a part of the string body.
+    pub fn shift(mut self, distance: usize) -> Self {
+        match &mut self {
+            EscapeError::IncompleteEscape { offset }
+            | EscapeError::UnknownEscape { offset, .. }
+            | EscapeError::InvalidHexEscape { offset }
+            | EscapeError::HexEscapeOutOfRange { offset, .. }
+            | EscapeError::UnicodeEscapeWithoutBrace { offset }
+            | EscapeError::UnterminatedUnicodeEscape { offset }
+            | EscapeError::EmptyUnicodeEscape { offset }
+            | EscapeError::InvalidUnicodeEscapeDigit { offset, .. }
+            | EscapeError::UnicodeEscapeTooLong { offset }
+            | EscapeError::InvalidCodePoint { offset, .. } => *offset += distance,
+        }
+        self
+    }
+}
+
+/// Maximum number of hexadecimal digits in a unicode escape.
+const MAX_UNICODE_DIGITS: usize = 6;
+
+/// Turn a string body into its runtime value by replacing all escape sequences with the characters they represent.
+///
+/// **Supported escape sequences:**
+/// * `\n`, `\r`, `\t`, `\0`, `\\`, `\'`, `\"`, `\$`.
+/// * `\xHH` where `HH` are exactly 2 hexadecimal digits of an ASCII character.
+/// * `\u{H...}` where `H...` are 1 to 6 hexadecimal digits of a unicode scalar value.
+///
+/// Cooking stops at the first malformed escape sequence.
+pub fn cook_escapes(body: &str) -> Result<String, EscapeError> {
+    let mut value = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(backslash) = rest.find('\\') {
+        value.push_str(&rest[..backslash]);
+        let offset = body.len() - rest.len() + backslash;
+        let (char, after) = parse_escape(offset, &rest[(backslash + 1)..])?;
+        value.push(char);
+        rest = after;
+    }
+    value.push_str(rest);
+    Ok(value)
+}
+
+/// Parse the part of an escape sequence after the backslash.
+fn parse_escape(offset: usize, input: &str) -> Result<(char, &'_ str), EscapeError> {
+    let (char, rest) = split_first_char(input).ok_or(EscapeError::IncompleteEscape { offset })?;
+    let char = match char {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        '\\' | '\'' | '"' | '$' => char,
+        'x' => return parse_hex_escape(offset, rest),
+        'u' => return parse_unicode_escape(offset, rest),
+        _ => return Err(EscapeError::UnknownEscape { offset, char }),
+    };
+    Ok((char, rest))
+}
+
+/// Parse the digits of a hex escape (`\xHH`).
+fn parse_hex_escape(offset: usize, input: &str) -> Result<(char, &'_ str), EscapeError> {
+    let digits = input
+        .get(..2)
+        .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+        .ok_or(EscapeError::InvalidHexEscape { offset })?;
+    let value = u8::from_str_radix(digits, 16).expect("digits were validated");
+    if !value.is_ascii() {
+        return Err(EscapeError::HexEscapeOutOfRange { offset, value });
+    }
+    Ok((value.into(), &input[2..]))
+}
+
+/// Parse the digits of a unicode escape (`\u{H...}`).
+fn parse_unicode_escape(offset: usize, input: &str) -> Result<(char, &'_ str), EscapeError> {
+    let input = input
+        .strip_prefix('{')
+        .ok_or(EscapeError::UnicodeEscapeWithoutBrace { offset })?;
+    let (digits, rest) = input
+        .split_once('}')
+        .ok_or(EscapeError::UnterminatedUnicodeEscape { offset })?;
+    if digits.is_empty() {
+        return Err(EscapeError::EmptyUnicodeEscape { offset });
+    }
+    if let Some(char) = digits.chars().find(|char| !char.is_ascii_hexdigit()) {
+        return Err(EscapeError::InvalidUnicodeEscapeDigit { offset, char });
+    }
+    if digits.len() > MAX_UNICODE_DIGITS {
+        return Err(EscapeError::UnicodeEscapeTooLong { offset });
+    }
+    let value = u32::from_str_radix(digits, 16).expect("digits were validated");
+    let char = char::from_u32(value).ok_or(EscapeError::InvalidCodePoint { offset, value })?;
+    Ok((char, rest))
+}
+
+impl<Content> StringToken<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Turn the body of the string into its runtime value.
+    ///
+    /// See [`cook_escapes`].
+    pub fn cook(&self) -> Result<String, EscapeError> {
+        cook_escapes(self.body.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        macro_rules! case {
+            ($input:literal -> $output:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(cook_escapes($input).as_deref(), Ok($output));
+            }};
+        }
+
+        case!("" -> "");
+        case!("abc def ghi" -> "abc def ghi");
+        case!(r"abc def \' ghi\n\t" -> "abc def ' ghi\n\t");
+        case!(r#"abc def \" ghi"# -> r#"abc def " ghi"#);
+        case!(r"\\\n\r\t\0" -> "\\\n\r\t\0");
+        case!(r"\x41\x7a\x7F\x00" -> "Az\x7F\0");
+        case!(r"\u{41}\u{2764}\u{FE0F}" -> "A❤️");
+        case!(r"\u{10FFFF}\u{000041}" -> "\u{10FFFF}A");
+        case!(r"I \u{2764} 三十六計" -> "I ❤ 三十六計");
+        case!(r"\\u{41}" -> r"\u{41}");
+        case!(r"cost: \$$price" -> "cost: $$price");
+    }
+
+    #[test]
+    fn negative() {
+        macro_rules! case {
+            ($input:literal -> $error:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(cook_escapes($input), Err($error));
+            }};
+        }
+
+        use EscapeError::*;
+        case!(r"abc\" -> IncompleteEscape { offset: 3 });
+        case!(r"abc\q" -> UnknownEscape { offset: 3, char: 'q' });
+        case!(r"❤️\❤️" -> UnknownEscape { offset: 6, char: '❤' });
+        case!(r"\n\a" -> UnknownEscape { offset: 2, char: 'a' });
+        case!(r"\x4" -> InvalidHexEscape { offset: 0 });
+        case!(r"\x4g" -> InvalidHexEscape { offset: 0 });
+        case!(r"\x" -> InvalidHexEscape { offset: 0 });
+        case!(r"\x❤" -> InvalidHexEscape { offset: 0 });
+        case!(r"\x80" -> HexEscapeOutOfRange { offset: 0, value: 0x80 });
+        case!(r"\xFF" -> HexEscapeOutOfRange { offset: 0, value: 0xFF });
+        case!(r"ab\u41" -> UnicodeEscapeWithoutBrace { offset: 2 });
+        case!(r"ab\u" -> UnicodeEscapeWithoutBrace { offset: 2 });
+        case!(r"ab\u{41" -> UnterminatedUnicodeEscape { offset: 2 });
+        case!(r"ab\u{}" -> EmptyUnicodeEscape { offset: 2 });
+        case!(r"ab\u{4g}" -> InvalidUnicodeEscapeDigit { offset: 2, char: 'g' });
+        case!(r"ab\u{41 \u{42}" -> InvalidUnicodeEscapeDigit { offset: 2, char: ' ' });
+        case!(r"ab\u{0000041}" -> UnicodeEscapeTooLong { offset: 2 });
+        case!(r"ab\u{D800}" -> InvalidCodePoint { offset: 2, value: 0xD800 });
+        case!(r"ab\u{110000}" -> InvalidCodePoint { offset: 2, value: 0x110000 });
+    }
+
+    #[test]
+    fn offset() {
+        let error = cook_escapes(r"abc\u{D800}").unwrap_err();
+        assert_eq!(error.offset(), 3);
+        let error = error.shift(4);
+        assert_eq!(
+            error,
+            EscapeError::InvalidCodePoint {
+                offset: 7,
+                value: 0xD800
+            }
+        );
+        assert_eq!(error.offset(), 7);
+    }
+
+    #[test]
+    fn string_token() {
+        use crate::token::ParseMiddleToken;
+        let (token, _) = StringToken::parse(r"prefix'abc def \' ghi\n\t'suffix").unwrap();
+        assert_eq!(token.cook().as_deref(), Ok("abc def ' ghi\n\t"));
+        let (token, _) = StringToken::parse(r"'abc\q'").unwrap();
+        assert_eq!(
+            token.cook(),
+            Err(EscapeError::UnknownEscape {
+                offset: 3,
+                char: 'q',
+            }),
+        );
+    }
+}
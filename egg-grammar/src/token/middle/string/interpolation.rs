@@ -0,0 +1,334 @@
+use super::StringToken;
+use crate::token::{
+    bracket::{BracketDirection, BracketShape},
+    BracketToken, MiddleToken, ParseMiddleToken, WordToken,
+};
+use derive_more::{Display, Error};
+use split_first_char::split_first_char;
+
+/// Prefix that marks a [`StringToken`] as a format string.
+pub const FORMAT_PREFIX: &str = "f";
+
+/// Segment of the body of a format string.
+///
+/// Every segment carries the byte offset of its content relative to the start of the string body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatSegment<Content> {
+    /// Literal text between interpolations.
+    ///
+    /// Escape sequences are kept as-is, use [`cook_escapes`](super::cook_escapes) to get the runtime value.
+    Literal { offset: usize, text: Content },
+    /// Interpolation of an identifier (`$ident`).
+    ///
+    /// `offset` points to the identifier, the `$` is 1 byte before it.
+    Identifier { offset: usize, name: Content },
+    /// Interpolation of an arbitrary expression (`${expr}`).
+    ///
+    /// `offset` points to the expression, the `${` is 2 bytes before it.
+    /// The expression can be re-tokenized with [`MiddleToken::parse`].
+    Expression { offset: usize, expr: Content },
+}
+
+impl<Content> FormatSegment<Content> {
+    /// Byte offset of the content of the segment relative to the start of the string body.
+    pub const fn offset(&self) -> usize {
+        match self {
+            FormatSegment::Literal { offset, .. }
+            | FormatSegment::Identifier { offset, .. }
+            | FormatSegment::Expression { offset, .. } => *offset,
+        }
+    }
+
+    /// Content of the segment.
+    pub const fn content(&self) -> &'_ Content {
+        match self {
+            FormatSegment::Literal { text, .. } => text,
+            FormatSegment::Identifier { name, .. } => name,
+            FormatSegment::Expression { expr, .. } => expr,
+        }
+    }
+}
+
+/// Error when splitting the body of a format string into [`FormatSegment`]s.
+///
+/// Every variant carries the byte offset of the `$` that started the malformed interpolation.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InterpolationError {
+    #[display(fmt = "Interpolation at {offset} is not terminated with a closing curly bracket")]
+    UnterminatedExpression { offset: usize },
+    #[display(fmt = "Interpolation at {offset} has no expression")]
+    EmptyExpression { offset: usize },
+}
+
+impl InterpolationError {
+    /// Byte offset of the `$` that started the malformed interpolation.
+    pub const fn offset(&self) -> usize {
+        match self {
+            InterpolationError::UnterminatedExpression { offset }
+            | InterpolationError::EmptyExpression { offset } => *offset,
+        }
+    }
+}
+
+/// Split the body of a format string into literal segments and interpolations.
+///
+/// **Syntax:**
+/// * `$ident` interpolates an identifier. A `$` that isn't followed by an identifier or `{` is literal text.
+/// * `${expr}` interpolates an expression. Curly brackets inside the expression must be balanced.
+/// * `\$` is a literal `$`.
+pub fn split_format_segments(
+    body: &str,
+) -> Result<Vec<FormatSegment<&'_ str>>, InterpolationError> {
+    let mut segments = Vec::new();
+    let mut literal_start = 0;
+    let mut rest = body;
+
+    let push_literal = |segments: &mut Vec<_>, start: usize, end: usize| {
+        if start < end {
+            let text = &body[start..end];
+            segments.push(FormatSegment::Literal {
+                offset: start,
+                text,
+            });
+        }
+    };
+
+    while let Some((char, after)) = split_first_char(rest) {
+        let offset = body.len() - rest.len();
+        match char {
+            '\\' => {
+                // skip the escaped character so that `\$` is never an interpolation
+                rest = split_first_char(after).map_or(after, |(_, after)| after);
+            }
+            '$' => {
+                if let Some(after) = after.strip_prefix('{') {
+                    let expr_offset = offset + 2;
+                    let expr = find_expression(after)
+                        .ok_or(InterpolationError::UnterminatedExpression { offset })?;
+                    if expr.trim().is_empty() {
+                        return Err(InterpolationError::EmptyExpression { offset });
+                    }
+                    push_literal(&mut segments, literal_start, offset);
+                    segments.push(FormatSegment::Expression {
+                        offset: expr_offset,
+                        expr,
+                    });
+                    rest = &after[(expr.len() + 1)..];
+                    literal_start = body.len() - rest.len();
+                } else if let Some((WordToken::Identifier(name), after)) = WordToken::parse(after) {
+                    push_literal(&mut segments, literal_start, offset);
+                    segments.push(FormatSegment::Identifier {
+                        offset: offset + 1,
+                        name,
+                    });
+                    rest = after;
+                    literal_start = body.len() - rest.len();
+                } else {
+                    rest = after;
+                }
+            }
+            _ => rest = after,
+        }
+    }
+
+    push_literal(&mut segments, literal_start, body.len());
+    Ok(segments)
+}
+
+/// Find the expression before the closing curly bracket that matches the (already consumed) opening one.
+///
+/// The expression is tokenized with [`MiddleToken::parse`] so that brackets inside nested strings are ignored.
+fn find_expression(input: &str) -> Option<&'_ str> {
+    let mut depth = 0usize;
+    let mut rest = input;
+    loop {
+        let Some((token, after)) = MiddleToken::parse(rest) else {
+            let (_, after) = split_first_char(rest)?; // skip invalid character
+            rest = after;
+            continue;
+        };
+        if let MiddleToken::Bracket(BracketToken {
+            direction,
+            shape: BracketShape::Curly,
+        }) = token
+        {
+            match direction {
+                BracketDirection::Open => depth += 1,
+                BracketDirection::Close if depth == 0 => {
+                    let len = input.len() - rest.len();
+                    return Some(&input[..len]);
+                }
+                BracketDirection::Close => depth -= 1,
+            }
+        }
+        rest = after;
+    }
+}
+
+impl<'a> StringToken<&'a str> {
+    /// Check if the string is a format string.
+    pub fn is_format(&self) -> bool {
+        self.prefix == FORMAT_PREFIX
+    }
+
+    /// Split the body of a format string into literal segments and interpolations.
+    ///
+    /// Return `None` if the string is not a format string.
+    ///
+    /// See [`split_format_segments`].
+    pub fn format_segments(
+        &self,
+    ) -> Option<Result<Vec<FormatSegment<&'a str>>, InterpolationError>> {
+        self.is_format().then(|| split_format_segments(self.body))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::{
+        number::{DecimalToken, IntegerToken},
+        string::Quote,
+        NumberToken, OperatorToken, PunctuationToken, WhitespaceToken,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        use FormatSegment::*;
+
+        macro_rules! case {
+            ($input:literal -> [$($segment:expr),* $(,)?]) => {{
+                eprintln!("TEST: {:?}", $input);
+                let segments = split_format_segments($input).unwrap();
+                assert_eq!(segments, [$($segment),*]);
+                for segment in segments {
+                    let offset = segment.offset();
+                    let content = *segment.content();
+                    assert_eq!(&$input[offset..(offset + content.len())], content);
+                }
+            }};
+        }
+
+        case!("" -> []);
+        case!("abc def" -> [Literal { offset: 0, text: "abc def" }]);
+        case!("Calculating fibonacci($n)..." -> [
+            Literal { offset: 0, text: "Calculating fibonacci(" },
+            Identifier { offset: 23, name: "n" },
+            Literal { offset: 24, text: ")..." },
+        ]);
+        case!("$a$b" -> [
+            Identifier { offset: 1, name: "a" },
+            Identifier { offset: 3, name: "b" },
+        ]);
+        case!("sum: ${a + b}!" -> [
+            Literal { offset: 0, text: "sum: " },
+            Expression { offset: 7, expr: "a + b" },
+            Literal { offset: 13, text: "!" },
+        ]);
+        case!("${ {a: 1}[\"}\"] }" -> [
+            Expression { offset: 2, expr: " {a: 1}[\"}\"] " },
+        ]);
+        case!("${a ❤ b}" -> [Expression { offset: 2, expr: "a ❤ b" }]);
+        case!(r"cost: \$$price" -> [
+            Literal { offset: 0, text: r"cost: \$" },
+            Identifier { offset: 9, name: "price" },
+        ]);
+        case!(r"\\$a" -> [
+            Literal { offset: 0, text: r"\\" },
+            Identifier { offset: 3, name: "a" },
+        ]);
+        case!("$ $1 $if $" -> [Literal { offset: 0, text: "$ $1 $if $" }]);
+        case!("$first-name!" -> [
+            Identifier { offset: 1, name: "first-name" },
+            Literal { offset: 11, text: "!" },
+        ]);
+    }
+
+    #[test]
+    fn negative() {
+        use InterpolationError::*;
+
+        macro_rules! case {
+            ($input:literal -> $error:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(split_format_segments($input), Err($error));
+            }};
+        }
+
+        case!("abc ${a + b" -> UnterminatedExpression { offset: 4 });
+        case!("${ {a: 1 }" -> UnterminatedExpression { offset: 0 });
+        case!("${\"}" -> UnterminatedExpression { offset: 0 });
+        case!("abc ${}" -> EmptyExpression { offset: 4 });
+        case!("${  }" -> EmptyExpression { offset: 0 });
+    }
+
+    #[test]
+    fn re_tokenize() {
+        let body = "${foo(a, \"}\") + 1}";
+        let [FormatSegment::Expression { expr, .. }] = split_format_segments(body).unwrap()[..]
+        else {
+            panic!("expecting a single expression");
+        };
+        let mut tokens = Vec::new();
+        let mut rest = expr;
+        while let Some((token, after)) = MiddleToken::parse(rest) {
+            tokens.push(token);
+            rest = after;
+        }
+        assert_eq!(rest, "");
+        let bracket = |direction, shape| MiddleToken::from(BracketToken { direction, shape });
+        let whitespace = MiddleToken::from(WhitespaceToken(" "));
+        let expected: Vec<MiddleToken<&str>> = vec![
+            WordToken::Identifier("foo").into(),
+            bracket(BracketDirection::Open, BracketShape::Round),
+            WordToken::Identifier("a").into(),
+            PunctuationToken::Comma.into(),
+            whitespace,
+            StringToken {
+                prefix: "",
+                suffix: "",
+                body: "}",
+                quote: Quote::Double,
+                error: None,
+            }
+            .into(),
+            bracket(BracketDirection::Close, BracketShape::Round),
+            whitespace,
+            OperatorToken("+").into(),
+            whitespace,
+            NumberToken {
+                body: IntegerToken::from(DecimalToken("1")).into(),
+                suffix: None,
+            }
+            .into(),
+        ];
+        assert_eq!(tokens, expected);
+    }
+
+    #[test]
+    fn string_token() {
+        let (token, _) = StringToken::parse("f'Calculating fibonacci($n)...'").unwrap();
+        assert!(token.is_format());
+        assert_eq!(
+            token.format_segments(),
+            Some(Ok(vec![
+                FormatSegment::Literal {
+                    offset: 0,
+                    text: "Calculating fibonacci(",
+                },
+                FormatSegment::Identifier {
+                    offset: 23,
+                    name: "n",
+                },
+                FormatSegment::Literal {
+                    offset: 24,
+                    text: ")...",
+                },
+            ])),
+        );
+        let (token, _) = StringToken::parse("'Calculating fibonacci($n)...'").unwrap();
+        assert!(!token.is_format());
+        assert_eq!(token.format_segments(), None);
+    }
+}
@@ -0,0 +1,269 @@
+mod builtin;
+
+pub use builtin::*;
+
+use super::{StringToken, FORMAT_PREFIX};
+use derive_more::{Display, Error};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+};
+
+/// What a prefix of a [`StringToken`] means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixKind {
+    /// Regular string without prefix (`'abc'`).
+    Plain,
+    /// Format string with interpolations (`f'abc $def'`).
+    Format,
+    /// Regular expression (`rg"[a-z]+"`).
+    Regex,
+    /// Glob pattern (`gl"src/**/*.rs"`).
+    Glob,
+    /// Abnormal identifier (`id'abc'`).
+    Identifier,
+    /// Identifier of another language (`idl"egg: abc"`).
+    LocalizedIdentifier,
+}
+
+/// Error reported by a [`BodyValidator`].
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+#[display(fmt = "{message}")]
+pub struct BodyError {
+    /// Byte offset of the error relative to the start of the string body.
+    pub offset: usize,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl BodyError {
+    /// Create a new error.
+    pub fn new(offset: usize, message: impl Into<String>) -> Self {
+        let message = message.into();
+        BodyError { offset, message }
+    }
+}
+
+/// Function that checks the body of a [`StringToken`].
+pub type BodyValidator = fn(&str) -> Result<(), BodyError>;
+
+/// Entry of a prefix in a [`PrefixRegistry`].
+#[derive(Clone, Copy)]
+pub struct PrefixEntry {
+    /// What the prefix means.
+    pub kind: PrefixKind,
+    /// Validator of the body of strings with the prefix.
+    pub validator: BodyValidator,
+}
+
+impl Debug for PrefixEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefixEntry")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error detected by [`PrefixRegistry::check`].
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+pub enum PrefixError {
+    #[display(fmt = "Unknown string prefix {_0:?}")]
+    UnknownPrefix(#[error(not(source))] String),
+    #[display(fmt = "Unknown string suffix {_0:?}")]
+    UnknownSuffix(#[error(not(source))] String),
+    #[display(fmt = "Invalid string body: {_0}")]
+    InvalidBody(#[error(not(source))] BodyError),
+}
+
+/// Result of [`PrefixRegistry::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixCheck {
+    /// Kind of the prefix (`None` if the prefix is unknown).
+    pub kind: Option<PrefixKind>,
+    /// All detected errors.
+    pub errors: Vec<PrefixError>,
+}
+
+/// Registry of the meanings of known prefixes and the list of known suffixes of [`StringToken`].
+#[derive(Debug, Default, Clone)]
+pub struct PrefixRegistry {
+    prefixes: HashMap<String, PrefixEntry>,
+    suffixes: HashSet<String>,
+}
+
+impl PrefixRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        PrefixRegistry::default()
+    }
+
+    /// Create a registry of all built-in prefixes.
+    ///
+    /// | Prefix | Kind                                    | Validator                        |
+    /// |--------|-----------------------------------------|----------------------------------|
+    /// | (none) | [`PrefixKind::Plain`]                   | [`validate_plain`]               |
+    /// | `f`    | [`PrefixKind::Format`]                  | [`validate_format`]              |
+    /// | `rg`   | [`PrefixKind::Regex`]                   | [`validate_regex`]               |
+    /// | `gl`   | [`PrefixKind::Glob`]                    | [`validate_glob`]                |
+    /// | `id`   | [`PrefixKind::Identifier`]              | [`validate_identifier`]          |
+    /// | `idl`  | [`PrefixKind::LocalizedIdentifier`]     | [`validate_localized_identifier`] |
+    pub fn builtin() -> Self {
+        let mut registry = PrefixRegistry::new();
+        registry.register_prefix("", PrefixKind::Plain, validate_plain);
+        registry.register_prefix(FORMAT_PREFIX, PrefixKind::Format, validate_format);
+        registry.register_prefix("rg", PrefixKind::Regex, validate_regex);
+        registry.register_prefix("gl", PrefixKind::Glob, validate_glob);
+        registry.register_prefix("id", PrefixKind::Identifier, validate_identifier);
+        registry.register_prefix(
+            "idl",
+            PrefixKind::LocalizedIdentifier,
+            validate_localized_identifier,
+        );
+        registry
+    }
+
+    /// Add or replace a prefix.
+    pub fn register_prefix(
+        &mut self,
+        prefix: impl Into<String>,
+        kind: PrefixKind,
+        validator: BodyValidator,
+    ) {
+        let entry = PrefixEntry { kind, validator };
+        self.prefixes.insert(prefix.into(), entry);
+    }
+
+    /// Add a suffix.
+    pub fn register_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffixes.insert(suffix.into());
+    }
+
+    /// Look up a prefix.
+    pub fn prefix(&self, prefix: &str) -> Option<&'_ PrefixEntry> {
+        self.prefixes.get(prefix)
+    }
+
+    /// Check if a suffix is known.
+    ///
+    /// An empty suffix is always known.
+    pub fn has_suffix(&self, suffix: &str) -> bool {
+        suffix.is_empty() || self.suffixes.contains(suffix)
+    }
+
+    /// Check the prefix, the suffix, and the body of a string token.
+    pub fn check<Content>(&self, token: &StringToken<Content>) -> PrefixCheck
+    where
+        Content: AsRef<str>,
+    {
+        let prefix = token.prefix.as_ref();
+        let suffix = token.suffix.as_ref();
+        let mut errors = Vec::new();
+
+        let entry = self.prefix(prefix);
+        match entry {
+            None => errors.push(PrefixError::UnknownPrefix(prefix.to_string())),
+            Some(entry) => {
+                if let Err(error) = (entry.validator)(token.body.as_ref()) {
+                    errors.push(PrefixError::InvalidBody(error));
+                }
+            }
+        }
+
+        if !self.has_suffix(suffix) {
+            errors.push(PrefixError::UnknownSuffix(suffix.to_string()));
+        }
+
+        let kind = entry.map(|entry| entry.kind);
+        PrefixCheck { kind, errors }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::ParseMiddleToken;
+    use pretty_assertions::assert_eq;
+
+    fn check(input: &str) -> PrefixCheck {
+        let (token, _) = StringToken::parse(input).expect("input should be a string token");
+        PrefixRegistry::builtin().check(&token)
+    }
+
+    #[test]
+    fn known_prefixes() {
+        macro_rules! case {
+            ($input:literal -> $kind:ident) => {{
+                eprintln!("TEST: {:?}", $input);
+                let expected = PrefixCheck {
+                    kind: Some(PrefixKind::$kind),
+                    errors: Vec::new(),
+                };
+                assert_eq!(check($input), expected);
+            }};
+        }
+
+        case!(r"'abc\n'" -> Plain);
+        case!("f'Calculating fibonacci($n)...'" -> Format);
+        case!(r#"rg"[a-z]+\d*""# -> Regex);
+        case!(r#"gl"src/**/*.rs""# -> Glob);
+        case!("id'abc def'" -> Identifier);
+        case!(r#"idl"egg: abc""# -> LocalizedIdentifier);
+    }
+
+    #[test]
+    fn errors() {
+        macro_rules! case {
+            ($input:literal -> $kind:expr, [$($error:expr),* $(,)?]) => {{
+                eprintln!("TEST: {:?}", $input);
+                let PrefixCheck { kind, errors } = check($input);
+                let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+                let expected: Vec<String> = vec![$($error.to_string()),*];
+                assert_eq!((kind, errors), ($kind, expected));
+            }};
+        }
+
+        case!("abc'def'" -> None, [r#"Unknown string prefix "abc""#]);
+        case!("'def'abc" -> Some(PrefixKind::Plain), [r#"Unknown string suffix "abc""#]);
+        case!("abc'def'ghi" -> None, [
+            r#"Unknown string prefix "abc""#,
+            r#"Unknown string suffix "ghi""#,
+        ]);
+        case!(r"'\q'" -> Some(PrefixKind::Plain), [
+            r"Invalid string body: Unknown escape sequence \q at 0",
+        ]);
+        case!("rg'[a-z'" -> Some(PrefixKind::Regex), [
+            "Invalid string body: unclosed character class",
+        ]);
+    }
+
+    #[test]
+    fn custom_registry() {
+        let mut registry = PrefixRegistry::new();
+        registry.register_prefix("sql", PrefixKind::Plain, |body| match body.is_empty() {
+            true => Err(BodyError::new(0, "empty query")),
+            false => Ok(()),
+        });
+        registry.register_suffix("x");
+
+        let (token, _) = StringToken::parse("sql'select 1'x").unwrap();
+        let expected = PrefixCheck {
+            kind: Some(PrefixKind::Plain),
+            errors: Vec::new(),
+        };
+        assert_eq!(registry.check(&token), expected);
+
+        let (token, _) = StringToken::parse("sql''").unwrap();
+        let expected = PrefixCheck {
+            kind: Some(PrefixKind::Plain),
+            errors: vec![PrefixError::InvalidBody(BodyError::new(0, "empty query"))],
+        };
+        assert_eq!(registry.check(&token), expected);
+
+        let (token, _) = StringToken::parse("'abc'").unwrap();
+        let expected = PrefixCheck {
+            kind: None,
+            errors: vec![PrefixError::UnknownPrefix(String::new())],
+        };
+        assert_eq!(registry.check(&token), expected);
+    }
+}
@@ -0,0 +1,243 @@
+use super::BodyError;
+use crate::token::string::{cook_escapes, split_format_segments, FormatSegment};
+use std::{iter::Peekable, str::CharIndices};
+
+/// Validate the body of a plain string.
+///
+/// All escape sequences must be well-formed.
+pub fn validate_plain(body: &str) -> Result<(), BodyError> {
+    cook_escapes(body)
+        .map(drop)
+        .map_err(|error| BodyError::new(error.offset(), error.to_string()))
+}
+
+/// Validate the body of a format string.
+///
+/// All interpolations and all escape sequences in the literal segments must be well-formed.
+pub fn validate_format(body: &str) -> Result<(), BodyError> {
+    let segments = split_format_segments(body)
+        .map_err(|error| BodyError::new(error.offset(), error.to_string()))?;
+    for segment in segments {
+        if let FormatSegment::Literal { offset, text } = segment {
+            cook_escapes(text).map_err(|error| {
+                let error = error.shift(offset);
+                BodyError::new(error.offset(), error.to_string())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate the body of a regular expression.
+///
+/// The syntax is that of [`regex_syntax`].
+pub fn validate_regex(body: &str) -> Result<(), BodyError> {
+    use regex_syntax::Error;
+    let error = match regex_syntax::Parser::new().parse(body) {
+        Ok(_) => return Ok(()),
+        Err(error) => error,
+    };
+    let (offset, message) = match &error {
+        Error::Parse(error) => (error.span().start.offset, error.kind().to_string()),
+        Error::Translate(error) => (error.span().start.offset, error.kind().to_string()),
+        _ => (0, error.to_string()),
+    };
+    Err(BodyError::new(offset, message))
+}
+
+/// Validate the body of a glob pattern.
+///
+/// **Syntax:**
+/// * `?` matches any character, `*` matches any sequence of characters except `/`.
+/// * `**` matches any sequence of path components, it must be a whole path component.
+/// * `[abc]`, `[a-z]`, `[!abc]` and `[^abc]` are character classes.
+/// * `{a,b}` matches either `a` or `b`, alternations cannot be nested.
+/// * `\` escapes the next character.
+pub fn validate_glob(body: &str) -> Result<(), BodyError> {
+    let mut chars = body.char_indices().peekable();
+    let mut alternation = None;
+    let mut prev = None;
+    while let Some((offset, char)) = chars.next() {
+        match char {
+            '\\' => {
+                if chars.next().is_none() {
+                    return Err(BodyError::new(offset, "dangling escape"));
+                }
+            }
+            '[' => validate_glob_class(offset, &mut chars)?,
+            '{' if alternation.is_some() => {
+                return Err(BodyError::new(
+                    offset,
+                    "nested alternations are not allowed",
+                ));
+            }
+            '{' => alternation = Some(offset),
+            '}' => {
+                if alternation.take().is_none() {
+                    return Err(BodyError::new(offset, "unopened alternation"));
+                }
+            }
+            '*' if chars.next_if(|(_, char)| *char == '*').is_some() => {
+                let is_separator = |char: Option<char>| match char {
+                    None | Some('/') => true,
+                    Some('{' | ',' | '}') => alternation.is_some(),
+                    _ => false,
+                };
+                let next = chars.peek().map(|(_, char)| *char);
+                if !is_separator(prev) || !is_separator(next) {
+                    return Err(BodyError::new(offset, "** must be a whole path component"));
+                }
+                prev = Some('*');
+                continue;
+            }
+            _ => {}
+        }
+        prev = Some(char);
+    }
+    match alternation {
+        Some(offset) => Err(BodyError::new(offset, "unclosed alternation")),
+        None => Ok(()),
+    }
+}
+
+/// Validate the rest of a character class of a glob pattern after the opening `[`.
+fn validate_glob_class(start: usize, chars: &mut Peekable<CharIndices>) -> Result<(), BodyError> {
+    chars.next_if(|(_, char)| matches!(char, '!' | '^'));
+    let mut first = true;
+    let mut prev = None;
+    while let Some((offset, char)) = chars.next() {
+        match char {
+            ']' if !first => return Ok(()),
+            '-' if prev.is_some() && chars.peek().map_or(false, |(_, char)| *char != ']') => {
+                let range_start = prev.take().expect("checked");
+                let (_, range_end) = chars.next().expect("checked");
+                if range_start > range_end {
+                    let message = format!("invalid range {range_start}-{range_end}");
+                    return Err(BodyError::new(offset, message));
+                }
+            }
+            _ => prev = Some(char),
+        }
+        first = false;
+    }
+    Err(BodyError::new(start, "unclosed character class"))
+}
+
+/// Validate the body of an abnormal identifier.
+///
+/// The identifier must not be empty.
+pub fn validate_identifier(body: &str) -> Result<(), BodyError> {
+    validate_plain(body)?;
+    if body.is_empty() {
+        return Err(BodyError::new(0, "identifier is empty"));
+    }
+    Ok(())
+}
+
+/// Validate the body of a localized identifier.
+///
+/// The body must consist of a language name, a colon, and a non-empty identifier (e.g. `egg: abc`).
+pub fn validate_localized_identifier(body: &str) -> Result<(), BodyError> {
+    validate_plain(body)?;
+    let Some((language, identifier)) = body.split_once(':') else {
+        return Err(BodyError::new(0, "missing colon after the language name"));
+    };
+    if language.trim().is_empty() {
+        return Err(BodyError::new(0, "language name is empty"));
+    }
+    if identifier.trim().is_empty() {
+        return Err(BodyError::new(language.len() + 1, "identifier is empty"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    macro_rules! def_case {
+        ($validate:ident) => {
+            macro_rules! case {
+                ($input:literal -> Ok) => {{
+                    eprintln!("TEST: {:?}", $input);
+                    assert_eq!($validate($input), Ok(()));
+                }};
+                ($input:literal -> $offset:literal, $message:literal) => {{
+                    eprintln!("TEST: {:?}", $input);
+                    assert_eq!($validate($input), Err(BodyError::new($offset, $message)));
+                }};
+            }
+        };
+    }
+
+    #[test]
+    fn plain() {
+        def_case!(validate_plain);
+        case!("" -> Ok);
+        case!(r"abc\n\u{41}" -> Ok);
+        case!(r"abc\q" -> 3, r"Unknown escape sequence \q at 3");
+    }
+
+    #[test]
+    fn format() {
+        def_case!(validate_format);
+        case!("abc $def ${ghi + 1}" -> Ok);
+        case!(r"abc ${def" -> 4, "Interpolation at 4 is not terminated with a closing curly bracket");
+        case!(r"abc $def \q" -> 9, r"Unknown escape sequence \q at 9");
+    }
+
+    #[test]
+    fn regex() {
+        def_case!(validate_regex);
+        case!("" -> Ok);
+        case!(r"[a-z]+\d*" -> Ok);
+        case!(r"^(?P<name>\w+)\s*=\s*\x22.*\x22$" -> Ok);
+        case!(r"abc[a-z" -> 3, "unclosed character class");
+        case!(r"abc(def" -> 3, "unclosed group");
+        case!(r"abc\q" -> 3, "unrecognized escape sequence");
+        case!(r"a{3,1}" -> 1, "invalid repetition count range, the start must be <= the end");
+        case!(r"abc)" -> 3, "unopened group");
+    }
+
+    #[test]
+    fn glob() {
+        def_case!(validate_glob);
+        case!("" -> Ok);
+        case!("src/**/*.rs" -> Ok);
+        case!("**" -> Ok);
+        case!("**/*.{rs,toml}" -> Ok);
+        case!("{**,src/**}/*.rs" -> Ok);
+        case!("[a-z]?[!0-9][^]][]-]" -> Ok);
+        case!(r"\*\[\{" -> Ok);
+        case!("src/a**/b" -> 5, "** must be a whole path component");
+        case!("src/**b" -> 4, "** must be a whole path component");
+        case!("***" -> 0, "** must be a whole path component");
+        case!("src/[a-z" -> 4, "unclosed character class");
+        case!("src/[]" -> 4, "unclosed character class");
+        case!("[z-a]" -> 2, "invalid range z-a");
+        case!("{a,{b,c}}" -> 3, "nested alternations are not allowed");
+        case!("{a,b" -> 0, "unclosed alternation");
+        case!("a,b}" -> 3, "unopened alternation");
+        case!(r"abc\" -> 3, "dangling escape");
+    }
+
+    #[test]
+    fn identifier() {
+        def_case!(validate_identifier);
+        case!("abc def" -> Ok);
+        case!(r"\u{41}" -> Ok);
+        case!("" -> 0, "identifier is empty");
+        case!(r"\q" -> 0, r"Unknown escape sequence \q at 0");
+    }
+
+    #[test]
+    fn localized_identifier() {
+        def_case!(validate_localized_identifier);
+        case!("egg: abc" -> Ok);
+        case!("rust:abc" -> Ok);
+        case!("abc" -> 0, "missing colon after the language name");
+        case!(" : abc" -> 0, "language name is empty");
+        case!("egg: " -> 4, "identifier is empty");
+    }
+}
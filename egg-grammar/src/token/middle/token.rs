@@ -1,6 +1,6 @@
 use super::{
-    BracketToken, NumberToken, OperatorToken, PunctuationToken, StringToken, WhitespaceToken,
-    WordToken,
+    BracketToken, IdentifierMode, NumberToken, OperatorToken, PunctuationToken, StringToken,
+    WhitespaceToken, WordToken,
 };
 use crate::token::ParseMiddleToken;
 use derive_more::{From, TryInto};
@@ -20,6 +20,8 @@ pub enum MiddleToken<Content> {
     /// * Exotic syntax: `rg"[a-z]+"`, `gl"src/**/*.rs"`, etc.
     /// * Abnormal identifier: `id"abc"`, `id'abc'`, `idl"egg: abc"`, `idl'egg: abc'`, etc.
     ///
+    /// The meanings of the prefixes are looked up in a [`PrefixRegistry`](super::string::PrefixRegistry).
+    ///
     /// **Excluding**
     ///
     /// * Multi-line string.
@@ -61,8 +63,9 @@ pub enum MiddleToken<Content> {
     Operator(OperatorToken<Content>),
 }
 
-impl<'a> ParseMiddleToken<&'a str> for MiddleToken<&'a str> {
-    fn parse(input: &'a str) -> Option<(Self, &'a str)> {
+impl<'a> MiddleToken<&'a str> {
+    /// Parse a token whose identifiers are allowed by `mode`.
+    pub fn parse_with(input: &'a str, mode: IdentifierMode) -> Option<(Self, &'a str)> {
         macro_rules! try_parse {
             ($token_type:ident) => {
                 if let Some((token, rest)) = $token_type::parse(input) {
@@ -72,7 +75,9 @@ impl<'a> ParseMiddleToken<&'a str> for MiddleToken<&'a str> {
         }
         try_parse!(WhitespaceToken);
         try_parse!(StringToken);
-        try_parse!(WordToken);
+        if let Some((token, rest)) = WordToken::parse_with(input, mode) {
+            return Some((MiddleToken::from(token), rest));
+        }
         try_parse!(NumberToken);
         try_parse!(BracketToken);
         try_parse!(PunctuationToken);
@@ -81,6 +86,12 @@ impl<'a> ParseMiddleToken<&'a str> for MiddleToken<&'a str> {
     }
 }
 
+impl<'a> ParseMiddleToken<&'a str> for MiddleToken<&'a str> {
+    fn parse(input: &'a str) -> Option<(Self, &'a str)> {
+        MiddleToken::parse_with(input, IdentifierMode::Ascii)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
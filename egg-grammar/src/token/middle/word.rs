@@ -1,6 +1,9 @@
 use crate::{keyword::Keyword, token::ParseMiddleToken};
 use derive_more::{From, TryInto};
-use egg_common_utils::{char_matcher, split_hbt_ascii};
+use egg_common_utils::{char_matcher, split_hbt, split_hbt_ascii};
+use std::borrow::Cow;
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 /// Token of an identifier or a keyword.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, From, TryInto)]
@@ -48,6 +51,27 @@ where
             WordToken::Keyword(keyword) => keyword.as_ref(),
         }
     }
+
+    /// Get the [NFC](https://unicode.org/reports/tr15/) form of the word.
+    ///
+    /// Identifiers that look the same may be encoded differently (e.g. `é` as a single code point
+    /// or as `e` followed by a combining accent), their NFC forms are the same.
+    pub fn normalize(&self) -> Cow<'_, str> {
+        let word = self.as_str();
+        if is_nfc(word) {
+            Cow::Borrowed(word)
+        } else {
+            Cow::Owned(word.nfc().collect())
+        }
+    }
+
+    /// Check if two words are the same after [normalization](WordToken::normalize).
+    pub fn is_equivalent_to<Other>(&self, other: &WordToken<Other>) -> bool
+    where
+        Other: AsRef<str>,
+    {
+        self.normalize() == other.normalize()
+    }
 }
 
 // Q: Why not generic over `T: AsRef<str>`?
@@ -68,16 +92,38 @@ where
     }
 }
 
-fn parse_word(input: &str) -> (&'_ str, &'_ str) {
-    char_matcher!(is_word_head => 'a'..='z' | 'A'..='Z' | '_');
-    char_matcher!(is_word_body => 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-');
-    char_matcher!(is_word_tail => 'a'..='z' | 'A'..='Z' | '0'..='9' | '_');
-    split_hbt_ascii(input, is_word_head, is_word_body, is_word_tail)
+/// Which characters are allowed in identifiers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierMode {
+    /// Only ASCII letters, digits, and underscores.
+    #[default]
+    Ascii,
+    /// Characters with the Unicode properties `XID_Start` (first character) and `XID_Continue`
+    /// (the rest), as well as underscores.
+    Unicode,
 }
 
-impl<'a> ParseMiddleToken<&'a str> for WordToken<&'a str> {
-    fn parse(input: &'a str) -> Option<(Self, &'a str)> {
-        let (word, rest) = parse_word(input);
+fn parse_word(input: &str, mode: IdentifierMode) -> (&'_ str, &'_ str) {
+    match mode {
+        IdentifierMode::Ascii => {
+            char_matcher!(is_word_head => 'a'..='z' | 'A'..='Z' | '_');
+            char_matcher!(is_word_body => 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-');
+            char_matcher!(is_word_tail => 'a'..='z' | 'A'..='Z' | '0'..='9' | '_');
+            split_hbt_ascii(input, is_word_head, is_word_body, is_word_tail)
+        }
+        IdentifierMode::Unicode => {
+            let is_word_head = |char: &char| *char == '_' || is_xid_start(*char);
+            let is_word_body = |char: &char| *char == '-' || is_xid_continue(*char);
+            let is_word_tail = |char: &char| is_xid_continue(*char);
+            split_hbt(input, is_word_head, is_word_body, is_word_tail)
+        }
+    }
+}
+
+impl<'a> WordToken<&'a str> {
+    /// Parse a word whose characters are allowed by `mode`.
+    pub fn parse_with(input: &'a str, mode: IdentifierMode) -> Option<(Self, &'a str)> {
+        let (word, rest) = parse_word(input, mode);
         if word.is_empty() {
             return None;
         }
@@ -86,6 +132,12 @@ impl<'a> ParseMiddleToken<&'a str> for WordToken<&'a str> {
     }
 }
 
+impl<'a> ParseMiddleToken<&'a str> for WordToken<&'a str> {
+    fn parse(input: &'a str) -> Option<(Self, &'a str)> {
+        WordToken::parse_with(input, IdentifierMode::Ascii)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -133,4 +185,50 @@ mod test {
         case!("-abc");
         case!("âbc");
     }
+
+    #[test]
+    fn unicode() {
+        use Keyword::*;
+        let id = WordToken::Identifier;
+        let kw = WordToken::Keyword;
+
+        macro_rules! case {
+            ($input:literal -> $token:expr, $rest:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(
+                    WordToken::parse_with($input, IdentifierMode::Unicode),
+                    Some(($token, $rest)),
+                );
+            }};
+        }
+
+        case!("suffíx + 1" -> id("suffíx"), " + 1");
+        case!("abcđef" -> id("abcđef"), "");
+        case!("âbc" -> id("âbc"), "");
+        case!("độ-dài-tối-đa-" -> id("độ-dài-tối-đa"), "-");
+        case!("長さ(x)" -> id("長さ"), "(x)");
+        case!("_имя_ = 1" -> id("_имя_"), " = 1");
+        case!("return 1" -> kw(Return), " 1");
+        case!("cafe\u{301}!" -> id("cafe\u{301}"), "!");
+
+        eprintln!("TEST: negative");
+        assert_eq!(WordToken::parse_with("3a", IdentifierMode::Unicode), None);
+        assert_eq!(WordToken::parse_with("-abc", IdentifierMode::Unicode), None);
+        assert_eq!(
+            WordToken::parse_with("\u{301}a", IdentifierMode::Unicode),
+            None
+        );
+        assert_eq!(WordToken::parse_with("→", IdentifierMode::Unicode), None);
+    }
+
+    #[test]
+    fn normalize() {
+        let composed = WordToken::from("caf\u{e9}");
+        let decomposed = WordToken::from("cafe\u{301}");
+        assert_ne!(composed, decomposed);
+        assert_eq!(decomposed.normalize(), "caf\u{e9}");
+        assert!(matches!(composed.normalize(), Cow::Borrowed("caf\u{e9}")));
+        assert!(composed.is_equivalent_to(&decomposed));
+        assert!(!composed.is_equivalent_to(&WordToken::from("cafe")));
+    }
 }
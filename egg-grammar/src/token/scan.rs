@@ -1,8 +1,14 @@
+mod rescan;
+
+pub use rescan::*;
+
 use super::{
-    EndingToken, IndentToken, InvalidToken, MiddleToken, ParseMiddleToken, TokenLine, TokenLineItem,
+    EndingToken, EolToken, IdentifierMode, IndentToken, InvalidToken, MiddleToken, ShebangToken,
+    TokenLine, TokenLineItem,
 };
-use split_first_char::split_first_char;
-use std::str::Lines;
+use crate::ast::{LnCol, Position, Span};
+use unicode_ident::is_xid_start;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 /// Token scanner.
 ///
@@ -12,66 +18,195 @@ pub struct Scan<'a> {
     state: State<'a>,
 }
 
+/// Options of [`Scan`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// Which characters are allowed in identifiers.
+    pub identifier_mode: IdentifierMode,
+}
+
 /// State of the scanner.
 #[derive(Debug)]
 struct State<'a> {
     lines: Lines<'a>,
+    options: ScanOptions,
 }
 
 impl<'a> Scan<'a> {
-    /// Start scanning text for tokens.
+    /// Start scanning text for tokens with the default options.
     pub fn new(text: &'a str) -> Self {
-        let lines = text.lines();
-        let state = State { lines };
+        Scan::with_options(text, ScanOptions::default())
+    }
+
+    /// Start scanning text for tokens.
+    pub fn with_options(text: &'a str, options: ScanOptions) -> Self {
+        let lines = Lines::new(text);
+        let state = State { lines, options };
+        Scan { state }
+    }
+
+    /// Resume scanning from the start of a line in the text.
+    fn resume(text: &'a str, start: Position, options: ScanOptions) -> Self {
+        let lines = Lines {
+            rest: &text[start.offset..],
+            next_start: start,
+        };
+        let state = State { lines, options };
         Scan { state }
     }
+
+    /// Options of the scanner.
+    pub fn options(&self) -> ScanOptions {
+        self.state.options
+    }
+
+    /// Position of the start of the next [`TokenLine`] (if there is one).
+    fn next_start(&self) -> Option<Position> {
+        let Lines { rest, next_start } = &self.state.lines;
+        (!rest.is_empty()).then_some(*next_start)
+    }
+}
+
+/// A line of text, its EOL, and the position of its first character.
+#[derive(Debug, Clone, Copy)]
+struct Line<'a> {
+    text: &'a str,
+    eol: Option<EolToken>,
+    start: Position,
+}
+
+impl<'a> Line<'a> {
+    /// Span of the whole line, not including the EOL.
+    fn span(&self) -> Span {
+        Span::inline(self.start, self.text)
+    }
+}
+
+/// Iterator over lines of text and their positions.
+///
+/// Lines are split according to `docs/specifications/misc/eol.md`.
+#[derive(Debug, Clone)]
+struct Lines<'a> {
+    rest: &'a str,
+    next_start: Position,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let next_start = Position::start_of_text();
+        Lines {
+            rest: text,
+            next_start,
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let start = self.next_start;
+        let (text, eol, rest) = EolToken::split_line(self.rest);
+        let eol_len = eol.map_or(0, |eol| eol.as_str().len());
+        self.rest = rest;
+        self.next_start.offset += text.len() + eol_len;
+        self.next_start.ln_col = LnCol::from_pred_counts(start.ln_col.line.pred_count() + 1, 0);
+        Some(Line { text, eol, start })
+    }
 }
 
 impl<'a> Iterator for Scan<'a> {
     type Item = TokenLine<&'a str>;
     fn next(&mut self) -> Option<Self::Item> {
         let Scan { state } = self;
-        let State { lines } = state;
-        let ln_text = lines.next()?;
+        let State { lines, options } = state;
+        let line = lines.next()?;
+        let ln_text = line.text;
+        let eol = line.eol;
         let (indent, rest) = IndentToken::parse(ln_text);
         let indent_src_text = &ln_text[..indent.len()];
-        let indent_item = TokenLineItem::new(indent_src_text, indent);
+        let indent_span = Span::inline(line.start, indent_src_text);
+        let indent_item = TokenLineItem::new(indent_src_text, indent, indent_span);
         let indent = &indent_item.token; // re-borrow a moved value
 
+        if line.start == Position::start_of_text() {
+            if let Some(token) = ShebangToken::parse(ln_text) {
+                let src_text = (ln_text, Vec::new());
+                let span = (line.span(), Vec::new());
+                let ending_item = TokenLineItem::new(src_text, token.into(), span);
+                let token_line =
+                    TokenLine::new(ln_text, eol, indent_item, Vec::new(), Some(ending_item));
+                return Some(token_line);
+            }
+        }
+
         let mut input = rest;
+        let mut position = indent_span.end;
         let mut middle = Vec::new();
 
         while !input.is_empty() {
             let mut lines_copy = lines.clone(); // to preserve the first non-body line (after the final body line)
-            let next_line = || lines_copy.next();
+            let next_line = || lines_copy.next().map(|line| line.text);
             let mut body_line_list = Vec::new();
+            let mut body_span_list = Vec::new();
             let after_parse = |body_line: &'a str| {
-                lines.next(); // the current line is proven to be belong in an ending token's body, it must be removed from `lines`.
-                body_line_list.push(body_line);
+                // the current line is proven to be belong in an ending token's body, it must be removed from `lines`.
+                let line = lines
+                    .next()
+                    .expect("body line was peeked from a copy of lines");
+                debug_assert_eq!(line.text, body_line);
+                body_line_list.push((body_line, line.eol));
+                body_span_list.push(line.span());
             };
 
-            if let Some(token) = EndingToken::build(indent, input, next_line, after_parse) {
+            if let Some(token) =
+                EndingToken::build(indent, input, !middle.is_empty(), next_line, after_parse)
+            {
                 middle.shrink_to_fit();
                 let src_text = (input, body_line_list);
-                let ending_item = TokenLineItem::new(src_text, token);
-                let token_line = TokenLine::new(ln_text, indent_item, middle, Some(ending_item));
+                let span = (Span::inline(position, input), body_span_list);
+                let ending_item = TokenLineItem::new(src_text, token, span);
+                let token_line =
+                    TokenLine::new(ln_text, eol, indent_item, middle, Some(ending_item));
                 return Some(token_line);
             }
 
-            if let Some((token, rest)) = MiddleToken::parse(input) {
+            if let Some((token, rest)) = parse_middle_token(input, options.identifier_mode) {
                 let token_len = input.len() - rest.len();
                 let src_text = &input[..token_len];
-                middle.push(TokenLineItem::new(src_text, Ok(token)));
+                let span = Span::inline(position, src_text);
+                middle.push(TokenLineItem::new(src_text, Ok(token), span));
                 input = rest;
+                position = span.end;
                 continue;
             }
 
-            if let Some((char, rest)) = split_first_char(input) {
-                let token = InvalidToken(char);
-                let char_len = char.len_utf8();
-                let src_text = &input[..char_len];
-                middle.push(TokenLineItem::new(src_text, Err(token)));
-                input = rest;
+            // a run of invalid characters ends before the first grapheme cluster that starts a token,
+            // only the clusters whose first characters may start a token are parsed, so a run of
+            // characters that can't start any token is scanned in linear time
+            let is_token_start = |input: &'a str| {
+                let mode = options.identifier_mode;
+                input
+                    .chars()
+                    .next()
+                    .map_or(false, |char| may_start_token(char, mode))
+                    && (parse_middle_token(input, mode).is_some()
+                        || EndingToken::build(indent, input, true, || None, |_| {}).is_some())
+            };
+            let mut graphemes = input.grapheme_indices(true);
+            if graphemes.next().is_some() {
+                let invalid_len = graphemes
+                    .map(|(offset, _)| offset)
+                    .find(|offset| is_token_start(&input[*offset..]))
+                    .unwrap_or(input.len());
+                let src_text = &input[..invalid_len];
+                let token = InvalidToken(src_text.chars().collect());
+                let span = Span::inline(position, src_text);
+                middle.push(TokenLineItem::new(src_text, Err(token), span));
+                input = &input[invalid_len..];
+                position = span.end;
                 continue;
             }
 
@@ -79,7 +214,46 @@ impl<'a> Iterator for Scan<'a> {
         }
 
         middle.shrink_to_fit();
-        let token_line = TokenLine::new(ln_text, indent_item, middle, None);
+        let token_line = TokenLine::new(ln_text, eol, indent_item, middle, None);
         Some(token_line)
     }
 }
+
+/// Check if a character may be the first character of a token.
+///
+/// Tokens other than Unicode identifiers start with ASCII characters.
+fn may_start_token(char: char, mode: IdentifierMode) -> bool {
+    char.is_ascii() || (mode == IdentifierMode::Unicode && is_xid_start(char))
+}
+
+/// Parse a middle token, a word never ends inside an extended grapheme cluster.
+///
+/// A word that ends inside a cluster (e.g. `e` in `e\u{301}`) is shortened to the boundary before
+/// its end, return `None` if the word is shorter than its first cluster. Other tokens are complete
+/// even when the next character extends their last cluster (e.g. `'abc'` in `'abc'\u{301}`),
+/// that character starts a run of invalid characters.
+fn parse_middle_token(
+    input: &str,
+    mode: IdentifierMode,
+) -> Option<(MiddleToken<&'_ str>, &'_ str)> {
+    let mut end = input.len();
+    loop {
+        let (token, rest) = MiddleToken::parse_with(&input[..end], mode)?;
+        let token_len = end - rest.len();
+        let rest = &input[token_len..];
+        // ASCII characters don't extend clusters because a line has no CR LF
+        if !matches!(token, MiddleToken::Word(_))
+            || rest.chars().next().map_or(true, |char| char.is_ascii())
+        {
+            return Some((token, rest));
+        }
+        let mut cursor = GraphemeCursor::new(token_len, input.len(), true);
+        if cursor.is_boundary(input, 0).unwrap_or(true) {
+            return Some((token, rest));
+        }
+        end = match cursor.prev_boundary(input, 0) {
+            Ok(Some(boundary)) if boundary > 0 => boundary,
+            _ => return None,
+        };
+    }
+}
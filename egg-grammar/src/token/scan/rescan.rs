@@ -0,0 +1,238 @@
+use super::{Scan, ScanOptions};
+use crate::{ast::Position, token::TokenLine};
+use std::ops::Range;
+
+/// Replacement of a range of text with another text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+    /// Byte range of the replaced text in the old text.
+    pub range: Range<usize>,
+    /// The text that replaced the range.
+    pub new_text: &'a str,
+}
+
+/// Result of [`Scan::rescan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rescan<Content> {
+    /// All token lines of the new text.
+    pub lines: Vec<TokenLine<Content>>,
+    /// Range of indices of the token lines in the old list that were replaced.
+    pub old_range: Range<usize>,
+    /// Range of indices of the token lines in [`Rescan::lines`] that were re-scanned.
+    pub new_range: Range<usize>,
+}
+
+impl<'a> Scan<'a> {
+    /// Re-scan only the lines that were affected by an edit.
+    ///
+    /// * `old_lines` are the token lines of the text before the edit.
+    /// * `new_text` is the whole text after the edit.
+    /// * `edit` is the edit that turned the old text into `new_text`.
+    ///
+    /// The token lines that weren't affected by the edit are reused, their spans are moved
+    /// to match `new_text`, but their contents still refer to the old text.
+    ///
+    /// `old_lines` are assumed to have been scanned with the default options,
+    /// use [`Scan::rescan_with_options`] otherwise.
+    pub fn rescan(
+        old_lines: Vec<TokenLine<&'a str>>,
+        new_text: &'a str,
+        edit: &TextEdit,
+    ) -> Rescan<&'a str> {
+        Scan::rescan_with_options(old_lines, new_text, edit, ScanOptions::default())
+    }
+
+    /// Re-scan only the lines that were affected by an edit with the same options
+    /// that `old_lines` were scanned with.
+    ///
+    /// See [`Scan::rescan`].
+    pub fn rescan_with_options(
+        mut old_lines: Vec<TokenLine<&'a str>>,
+        new_text: &'a str,
+        edit: &TextEdit,
+        options: ScanOptions,
+    ) -> Rescan<&'a str> {
+        let old_starts: Vec<usize> = old_lines
+            .iter()
+            .map(|line| line.indent.span.start.offset)
+            .collect();
+
+        // The line before the edited line is also re-scanned, because the edit may
+        // change whether the edited line belongs to the body of its ending token.
+        let first = old_starts
+            .partition_point(|&start| start <= edit.range.start)
+            .saturating_sub(2);
+        let restart = old_lines
+            .get(first)
+            .map_or_else(Position::start_of_text, |line| line.indent.span.start);
+        let new_edit_end = edit.range.start + edit.new_text.len();
+
+        let mut scan = Scan::resume(new_text, restart, options);
+        let mut scanned = Vec::new();
+        let mut reused = None;
+        while let Some(next_start) = scan.next_start() {
+            // The first line of either text is never reused, because it may be a shebang
+            // only when it is the first line.
+            if next_start.offset >= new_edit_end && next_start.offset != 0 {
+                let old_offset = next_start.offset - new_edit_end + edit.range.end;
+                let old_index = old_starts.binary_search(&old_offset).ok();
+                if let Some(index) = old_index.filter(|index| *index != 0) {
+                    reused = Some((index, next_start));
+                    break;
+                }
+            }
+            scanned.extend(scan.next());
+        }
+
+        let (last, tail) = match reused {
+            None => (old_lines.len(), Vec::new()),
+            Some((index, new_start)) => {
+                let mut tail = old_lines.split_off(index);
+                let old_start = tail[0].indent.span.start;
+                for line in &mut tail {
+                    line.rebase_spans(old_start, new_start);
+                }
+                (index, tail)
+            }
+        };
+        old_lines.truncate(last);
+
+        // Skip the re-scanned lines that turned out to be unchanged.
+        let unchanged = old_lines[first..]
+            .iter()
+            .zip(&scanned)
+            .take_while(|(old, new)| old == new)
+            .count();
+        let old_range = (first + unchanged)..last;
+        let new_range = (first + unchanged)..(first + scanned.len());
+
+        old_lines.truncate(first);
+        let mut lines = old_lines;
+        lines.extend(scanned);
+        lines.extend(tail);
+
+        Rescan {
+            lines,
+            old_range,
+            new_range,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::IdentifierMode;
+    use pretty_assertions::assert_eq;
+
+    /// Apply the edit to the old text, then compare the result of re-scanning with a full scan.
+    fn rescan(old_text: &str, range: Range<usize>, new_text: &str) -> (Range<usize>, Range<usize>) {
+        eprintln!();
+        eprintln!("TEST: {old_text:?} ({range:?} -> {new_text:?})");
+        let edited = format!(
+            "{}{new_text}{}",
+            &old_text[..range.start],
+            &old_text[range.end..],
+        );
+        let old_lines: Vec<_> = Scan::new(old_text).collect();
+        let edit = TextEdit { range, new_text };
+        let Rescan {
+            lines,
+            old_range,
+            new_range,
+        } = Scan::rescan(old_lines, &edited, &edit);
+        let expected: Vec<_> = Scan::new(&edited).collect();
+        assert_eq!(lines, expected);
+        (old_range, new_range)
+    }
+
+    #[test]
+    fn edit_single_line() {
+        let text = "let a = 1\nlet b = 2\nlet c = 3\n";
+        assert_eq!(rescan(text, 14..15, "bb"), (1..2, 1..2));
+        assert_eq!(rescan(text, 0..0, "# "), (0..1, 0..1));
+        assert_eq!(rescan(text, 28..29, ""), (2..3, 2..3));
+        assert_eq!(rescan(text, 30..30, "let d = 4"), (3..3, 3..4));
+    }
+
+    #[test]
+    fn add_and_remove_lines() {
+        let text = "let a = 1\nlet b = 2\nlet c = 3\n";
+        assert_eq!(rescan(text, 9..9, "\nlet x = 0"), (1..1, 1..2));
+        assert_eq!(rescan(text, 9..19, ""), (1..2, 1..1));
+        assert_eq!(rescan(text, 0..text.len(), ""), (0..3, 0..0));
+        assert_eq!(rescan("", 0..0, text), (0..0, 0..3));
+    }
+
+    #[test]
+    fn no_change() {
+        let text = "let a = 1\nlet b = 2\n";
+        assert_eq!(rescan(text, 4..5, "a"), (1..1, 1..1));
+        assert_eq!(rescan(text, 12..12, ""), (2..2, 2..2));
+    }
+
+    #[test]
+    fn text_block() {
+        let text = "let a = '''\n    abc\n    def\nprint(a)\n";
+
+        // dedenting a body line ends the text block early
+        assert_eq!(rescan(text, 20..24, ""), (0..1, 0..2));
+
+        // indenting the line after the block makes it a body line
+        assert_eq!(rescan(text, 28..28, "    "), (0..2, 0..1));
+
+        // removing the header turns the body lines into regular lines
+        assert_eq!(rescan(text, 8..11, "0"), (0..1, 0..3));
+
+        // starting a block swallows the lines after it
+        let text = "print(a)\n    abc\n    def\nprint(b)\n";
+        assert_eq!(rescan(text, 8..8, " '''"), (0..3, 0..1));
+    }
+
+    #[test]
+    fn doc_block() {
+        let text = "@@desc\n    abc\n  def\nfn main() do\n";
+
+        // fixing the indentation of a body line makes it join the block
+        assert_eq!(rescan(text, 15..15, "  "), (0..2, 0..1));
+
+        // editing a line after the block doesn't re-scan the block
+        assert_eq!(rescan(text, 24..28, "start"), (2..3, 2..3));
+    }
+
+    #[test]
+    fn shebang() {
+        // pushing the shebang down turns it into a comment
+        let text = "#! /usr/bin/env egg\nlet a = 1\n";
+        assert_eq!(rescan(text, 0..0, "# x\n"), (0..1, 0..2));
+
+        // pulling a comment up to the first line turns it into a shebang
+        let text = "let a = 1\n#! /usr/bin/env egg\n";
+        assert_eq!(rescan(text, 0..10, ""), (0..2, 0..1));
+    }
+
+    #[test]
+    fn crlf() {
+        let text = "let a = '''\r\n    abc\r\nprint(a)\r\n";
+        assert_eq!(rescan(text, 22..22, "    "), (0..2, 0..1));
+        assert_eq!(rescan(text, 11..13, "\n"), (0..1, 0..1));
+    }
+
+    #[test]
+    fn identifier_mode() {
+        let options = ScanOptions {
+            identifier_mode: IdentifierMode::Unicode,
+        };
+        let text = "let a = 1\nlet b = 2\n";
+        let edited = "let a = 1\nlet độ = 2\n";
+        let edit = TextEdit {
+            range: 14..15,
+            new_text: "độ",
+        };
+        let old_lines: Vec<_> = Scan::with_options(text, options).collect();
+        let Rescan { lines, .. } = Scan::rescan_with_options(old_lines, edited, &edit, options);
+        let expected: Vec<_> = Scan::with_options(edited, options).collect();
+        assert_eq!(lines, expected);
+        assert_ne!(lines, Scan::new(edited).collect::<Vec<_>>());
+    }
+}
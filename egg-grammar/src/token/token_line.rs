@@ -1,4 +1,5 @@
-use super::{EndingToken, IndentToken, InvalidToken, MiddleToken};
+use super::{EndingToken, EolToken, IndentToken, InvalidToken, MiddleToken};
+use crate::ast::{Position, Span};
 use derive_more::Constructor;
 use std::iter::once;
 
@@ -6,13 +7,22 @@ use std::iter::once;
 type MiddleTokenResult<Content> = Result<MiddleToken<Content>, InvalidToken>;
 
 /// [`TokenLineItem`] of an [`EndingToken`].
-type EndingTokenItem<Content> = TokenLineItem<(Content, Vec<Content>), EndingToken<Content>>;
+///
+/// Both the source text and the span are pairs of the header and the list of body lines.
+/// Each body line in the source text is paired with the EOL that terminated it.
+type EndingTokenItem<Content> = TokenLineItem<
+    (Content, Vec<(Content, Option<EolToken>)>),
+    EndingToken<Content>,
+    (Span, Vec<Span>),
+>;
 
 /// List of tokens from a line.
 #[derive(Debug, Clone, PartialEq, Eq, Constructor)]
 pub struct TokenLine<Content> {
     /// The content of the line.
     pub ln_text: Content,
+    /// The EOL that terminated the line (`None` if the line is the last line of the text).
+    pub eol: Option<EolToken>,
     /// Token of the indentation at the start of the line.
     pub indent: TokenLineItem<Content, IndentToken>,
     /// List of [`MiddleToken`] after indentation.
@@ -30,25 +40,70 @@ impl<Content> TokenLine<Content> {
     /// Iterate over all lines that were parsed into the body part of the ending token
     /// (if there is an ending token).
     pub fn ending_body_ln_text(&self) -> impl Iterator<Item = &'_ Content> + '_ {
-        self.ending
-            .iter()
-            .flat_map(|item| item.body_ln_text().iter())
+        self.ending.iter().flat_map(|item| item.body_ln_text())
+    }
+
+    /// Iterate over [`TokenLine::eol`] and the EOLs of [`TokenLine::ending_body_ln_text`].
+    pub fn all_eol(&self) -> impl Iterator<Item = Option<EolToken>> + '_ {
+        once(self.eol).chain(self.ending.iter().flat_map(|item| item.body_eol()))
+    }
+
+    /// Move all spans of the line by the same distance that it would take to move `from` to `to`.
+    ///
+    /// See [`Position::rebase`].
+    pub fn rebase_spans(&mut self, from: Position, to: Position) {
+        let rebase = |span: &mut Span| *span = span.rebase(from, to);
+        rebase(&mut self.indent.span);
+        self.middle
+            .iter_mut()
+            .map(|item| &mut item.span)
+            .for_each(rebase);
+        if let Some(ending) = &mut self.ending {
+            let (header, body) = &mut ending.span;
+            rebase(header);
+            body.iter_mut().for_each(rebase);
+        }
+    }
+
+    /// Iterate over all EOLs that are forbidden by the specification.
+    pub fn invalid_eol(&self) -> impl Iterator<Item = EolToken> + '_ {
+        self.all_eol().flatten().filter(|eol| !eol.is_valid())
     }
 }
 
 /// Item of [`TokenLine`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Constructor)]
-pub struct TokenLineItem<SrcText, Token> {
+pub struct TokenLineItem<SrcText, Token, SrcSpan = Span> {
     /// The original text that was parsed into the token.
     pub src_text: SrcText,
     /// The token that was parsed from the source text.
     pub token: Token,
+    /// Location of the source text.
+    pub span: SrcSpan,
 }
 
 impl<Content> EndingTokenItem<Content> {
     /// The original text that was parsed into the body part of the token.
-    pub fn body_ln_text(&self) -> &'_ Vec<Content> {
+    pub fn body_ln_text(&self) -> impl Iterator<Item = &'_ Content> + '_ {
         let (_, lines) = &self.src_text;
+        lines.iter().map(|(text, _)| text)
+    }
+
+    /// The EOLs that terminated the lines of the body part of the token.
+    pub fn body_eol(&self) -> impl Iterator<Item = Option<EolToken>> + '_ {
+        let (_, lines) = &self.src_text;
+        lines.iter().map(|(_, eol)| *eol)
+    }
+
+    /// Location of the header of the token.
+    pub fn header_span(&self) -> Span {
+        let (header, _) = self.span;
+        header
+    }
+
+    /// Locations of the lines that were parsed into the body part of the token.
+    pub fn body_spans(&self) -> &'_ Vec<Span> {
+        let (_, lines) = &self.span;
         lines
     }
 }
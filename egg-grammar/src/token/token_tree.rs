@@ -0,0 +1,297 @@
+use super::{
+    bracket::{BracketDirection, BracketShape},
+    InvalidToken, MiddleToken, TokenLine, TokenLineItem,
+};
+use crate::ast::{Position, Span};
+use derive_more::{Display, Error};
+
+/// [`TokenLineItem`] of a [`MiddleToken`] or an [`InvalidToken`].
+type MiddleItem<Content> = TokenLineItem<Content, Result<MiddleToken<Content>, InvalidToken>>;
+
+/// Token or a group of tokens in matching brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenTree<'a, Content> {
+    /// Token that is neither a bracket nor a whitespace.
+    Leaf(&'a MiddleItem<Content>),
+    /// Tokens between an open bracket and its close bracket.
+    Group(BracketGroup<'a, Content>),
+}
+
+/// Tokens between an open bracket and its close bracket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketGroup<'a, Content> {
+    /// Shape of the open bracket.
+    pub shape: BracketShape,
+    /// Location of the open bracket.
+    pub open: Span,
+    /// Location of the close bracket (`None` if the group is not closed).
+    pub close: Option<Span>,
+    /// Tokens between the brackets.
+    pub trees: Vec<TokenTree<'a, Content>>,
+}
+
+/// Token lines that are joined by open brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeLine<'a, Content> {
+    /// The joined token lines.
+    ///
+    /// The indentation of the first line is the indentation of the whole group,
+    /// the indentations of the other lines are insignificant.
+    pub lines: &'a [TokenLine<Content>],
+    /// Token trees of the joined lines.
+    pub trees: Vec<TokenTree<'a, Content>>,
+}
+
+/// Error of a bracket without a match.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BracketError {
+    /// Open bracket is closed by a close bracket of another shape.
+    #[display(
+        fmt = "Bracket at {} is closed by a bracket of another shape at {}",
+        "open.start.ln_col",
+        "close.start.ln_col"
+    )]
+    Mismatched {
+        /// Location of the open bracket.
+        open: Span,
+        /// Location of the close bracket.
+        close: Span,
+    },
+    /// Open bracket is not closed before the end of the text.
+    #[display(
+        fmt = "Bracket at {} is not closed before {}",
+        "open.start.ln_col",
+        "end.ln_col"
+    )]
+    Unclosed {
+        /// Location of the open bracket.
+        open: Span,
+        /// End of the last token of the text.
+        end: Position,
+    },
+    /// Close bracket has no open bracket.
+    #[display(fmt = "Bracket at {} has no open bracket", "close.start.ln_col")]
+    Unopened {
+        /// Location of the close bracket.
+        close: Span,
+    },
+}
+
+/// Result of [`build_token_trees`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenTrees<'a, Content> {
+    /// Groups of joined lines, in the order of the lines.
+    pub lines: Vec<TreeLine<'a, Content>>,
+    /// Brackets without matches.
+    pub errors: Vec<BracketError>,
+}
+
+/// Group of tokens whose close bracket is yet to be found.
+struct OpenGroup<'a, Content> {
+    shape: BracketShape,
+    open: Span,
+    trees: Vec<TokenTree<'a, Content>>,
+}
+
+/// Match the brackets of the token lines and group the tokens between them.
+///
+/// Newlines and indentations inside brackets are insignificant: the lines are joined until
+/// all open brackets are closed, so a group that isn't closed lasts until the end of the text.
+///
+/// A close bracket of another shape still closes the innermost group.
+/// A close bracket without an open bracket is kept as a [leaf](TokenTree::Leaf).
+pub fn build_token_trees<Content>(lines: &[TokenLine<Content>]) -> TokenTrees<'_, Content> {
+    let mut tree_lines = Vec::new();
+    let mut errors = Vec::new();
+    let mut index = 0;
+
+    while let Some(first) = lines.get(index) {
+        let start = index;
+        let mut stack: Vec<OpenGroup<Content>> = Vec::new();
+        let mut trees = Vec::new();
+        let mut end = first.indent.span.end;
+
+        loop {
+            for item in &lines[index].middle {
+                end = item.span.end;
+                let bracket = match &item.token {
+                    Ok(MiddleToken::Whitespace(_)) => continue,
+                    Ok(MiddleToken::Bracket(bracket)) => bracket,
+                    _ => {
+                        let parent = stack
+                            .last_mut()
+                            .map_or(&mut trees, |group| &mut group.trees);
+                        parent.push(TokenTree::Leaf(item));
+                        continue;
+                    }
+                };
+                match bracket.direction {
+                    BracketDirection::Open => stack.push(OpenGroup {
+                        shape: bracket.shape,
+                        open: item.span,
+                        trees: Vec::new(),
+                    }),
+                    BracketDirection::Close => {
+                        let Some(group) = stack.pop() else {
+                            errors.push(BracketError::Unopened { close: item.span });
+                            trees.push(TokenTree::Leaf(item));
+                            continue;
+                        };
+                        if group.shape != bracket.shape {
+                            errors.push(BracketError::Mismatched {
+                                open: group.open,
+                                close: item.span,
+                            });
+                        }
+                        let parent = stack
+                            .last_mut()
+                            .map_or(&mut trees, |group| &mut group.trees);
+                        parent.push(TokenTree::Group(BracketGroup {
+                            shape: group.shape,
+                            open: group.open,
+                            close: Some(item.span),
+                            trees: group.trees,
+                        }));
+                    }
+                }
+            }
+            index += 1;
+
+            if stack.is_empty() || index == lines.len() {
+                break;
+            }
+        }
+
+        while let Some(group) = stack.pop() {
+            errors.push(BracketError::Unclosed {
+                open: group.open,
+                end,
+            });
+            let parent = stack
+                .last_mut()
+                .map_or(&mut trees, |group| &mut group.trees);
+            parent.push(TokenTree::Group(BracketGroup {
+                shape: group.shape,
+                open: group.open,
+                close: None,
+                trees: group.trees,
+            }));
+        }
+
+        tree_lines.push(TreeLine {
+            lines: &lines[start..index],
+            trees,
+        });
+    }
+
+    TokenTrees {
+        lines: tree_lines,
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::Scan;
+    use pretty_assertions::assert_eq;
+
+    /// Write token trees as text with the brackets but without the whitespaces.
+    fn summarize(trees: &[TokenTree<'_, &str>]) -> String {
+        let trees = trees.iter().map(|tree| match tree {
+            TokenTree::Leaf(item) => item.src_text.to_string(),
+            TokenTree::Group(group) => {
+                let (open, close) = match group.shape {
+                    BracketShape::Round => ('(', ')'),
+                    BracketShape::Square => ('[', ']'),
+                    BracketShape::Curly => ('{', '}'),
+                };
+                let close = if group.close.is_some() { close } else { '…' };
+                format!("{open}{}{close}", summarize(&group.trees))
+            }
+        });
+        trees.collect::<Vec<_>>().join(" ")
+    }
+
+    /// Summarize the joined lines as pairs of the line count and the token trees,
+    /// followed by the errors.
+    fn build(text: &str) -> (Vec<(usize, String)>, Vec<String>) {
+        let lines: Vec<_> = Scan::new(text).collect();
+        let TokenTrees { lines, errors } = build_token_trees(&lines);
+        let lines = lines
+            .iter()
+            .map(|line| (line.lines.len(), summarize(&line.trees)))
+            .collect();
+        let errors = errors.iter().map(ToString::to_string).collect();
+        (lines, errors)
+    }
+
+    #[test]
+    fn single_line() {
+        let (lines, errors) = build("print(a, [b, {c}])\nlet d = ()\n");
+        let expected = [
+            (1, "print (a , [b , {c}])".to_string()),
+            (1, "let d = ()".to_string()),
+        ];
+        assert_eq!(
+            (lines.as_slice(), errors.as_slice()),
+            (&expected[..], &[][..])
+        );
+    }
+
+    #[test]
+    fn multiple_lines() {
+        let text = include_str!(
+            "../../tests/fixtures/examples/trait/trait-bound-positional-parameters.egg"
+        );
+        let (lines, errors) = build(text);
+        let received: Vec<_> = lines.iter().map(|(count, _)| *count).collect();
+        let expected = [1, 1, 1, 1, 1, 1, 1, 1, 1, 4, 1, 1, 1];
+        assert_eq!(received, expected);
+        assert_eq!(
+            lines[9].1,
+            "inst (Length , Height) Area (Rectangle (Length , Height) , Mul (Length , Height) :: Output ,)",
+        );
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn insignificant_indentation() {
+        let text = "    foo(\na,\n            b\n\n  )\n    bar()\n";
+        let expected = vec![(5, "foo (a , b)".to_string()), (1, "bar ()".to_string())];
+        assert_eq!(build(text), (expected, vec![]));
+    }
+
+    #[test]
+    fn mismatched() {
+        let text = "foo(a, [b)]\n";
+        let expected = vec![(1, "foo (a , [b])".to_string())];
+        let errors = vec![
+            "Bracket at 1:8 is closed by a bracket of another shape at 1:10".to_string(),
+            "Bracket at 1:4 is closed by a bracket of another shape at 1:11".to_string(),
+        ];
+        assert_eq!(build(text), (expected, errors));
+    }
+
+    #[test]
+    fn unclosed() {
+        let text = "foo(a,\n    b\nbar(c\n";
+        let expected = vec![(3, "foo (a , b bar (c……".to_string())];
+        let errors = vec![
+            "Bracket at 3:4 is not closed before 3:6".to_string(),
+            "Bracket at 1:4 is not closed before 3:6".to_string(),
+        ];
+        assert_eq!(build(text), (expected, errors));
+    }
+
+    #[test]
+    fn unopened() {
+        let text = "foo)\nbar]\n";
+        let expected = vec![(1, "foo )".to_string()), (1, "bar ]".to_string())];
+        let errors = vec![
+            "Bracket at 1:4 has no open bracket".to_string(),
+            "Bracket at 2:4 has no open bracket".to_string(),
+        ];
+        assert_eq!(build(text), (expected, errors));
+    }
+}
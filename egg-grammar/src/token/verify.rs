@@ -0,0 +1,247 @@
+mod json;
+mod yaml;
+
+pub use json::*;
+pub use yaml::*;
+
+use super::{EndingToken, TextToken, TokenLine};
+use crate::ast::{Position, Span};
+use derive_more::{Display, Error};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+};
+
+/// Diagnostic reported by a [`TextVerifier`].
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+#[display(fmt = "{message}")]
+pub struct TextDiagnostic {
+    /// Byte offset of the problem relative to the start of the dedented body.
+    pub offset: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl TextDiagnostic {
+    /// Create a new diagnostic.
+    pub fn new(offset: usize, message: impl Into<String>) -> Self {
+        let message = message.into();
+        TextDiagnostic { offset, message }
+    }
+}
+
+/// [`TextDiagnostic`] whose location was mapped back to the source text.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+#[display(fmt = "{}: {}", "position.ln_col", message)]
+pub struct SourceDiagnostic {
+    /// Location of the problem in the source text.
+    pub position: Position,
+    /// Description of the problem.
+    pub message: String,
+}
+
+/// Verifier of the body of a [multi-line string](TextToken) in an embedded language.
+pub trait TextVerifier {
+    /// Verify the dedented body of a multi-line string.
+    ///
+    /// The body lines are joined with `\n` regardless of the EOLs of the source text.
+    fn verify(&self, body: &str) -> Vec<TextDiagnostic>;
+}
+
+impl<Verify> TextVerifier for Verify
+where
+    Verify: Fn(&str) -> Vec<TextDiagnostic>,
+{
+    fn verify(&self, body: &str) -> Vec<TextDiagnostic> {
+        self(body)
+    }
+}
+
+/// Registry of [`TextVerifier`] by the language tags of multi-line strings (e.g. `yaml` in `'''yaml`).
+#[derive(Default)]
+pub struct TextVerifierRegistry {
+    verifiers: HashMap<String, Box<dyn TextVerifier>>,
+}
+
+impl Debug for TextVerifierRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextVerifierRegistry")
+            .field("languages", &self.verifiers.keys())
+            .finish()
+    }
+}
+
+impl TextVerifierRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        TextVerifierRegistry::default()
+    }
+
+    /// Create a registry of all built-in verifiers.
+    ///
+    /// | Language       | Verifier        |
+    /// |----------------|-----------------|
+    /// | `json`         | [`verify_json`] |
+    /// | `yaml`, `yml`  | [`verify_yaml`] |
+    pub fn builtin() -> Self {
+        let mut registry = TextVerifierRegistry::new();
+        registry.register("json", verify_json);
+        registry.register("yaml", verify_yaml);
+        registry.register("yml", verify_yaml);
+        registry
+    }
+
+    /// Add or replace the verifier of a language.
+    pub fn register(&mut self, language: impl Into<String>, verifier: impl TextVerifier + 'static) {
+        self.verifiers.insert(language.into(), Box::new(verifier));
+    }
+
+    /// Look up the verifier of a language.
+    pub fn get(&self, language: &str) -> Option<&'_ dyn TextVerifier> {
+        self.verifiers.get(language).map(AsRef::as_ref)
+    }
+
+    /// Verify the multi-line string at the end of a line.
+    ///
+    /// Multi-line strings without language tags or with unknown language tags are not verified.
+    pub fn verify_line<Content>(&self, line: &TokenLine<Content>) -> Vec<SourceDiagnostic>
+    where
+        Content: AsRef<str>,
+    {
+        let Some(ending) = &line.ending else {
+            return Vec::new();
+        };
+        let EndingToken::Text(token) = &ending.token else {
+            return Vec::new();
+        };
+        let Some(verifier) = token.language().and_then(|language| self.get(language)) else {
+            return Vec::new();
+        };
+
+        let body = token.dedented_lines().collect::<Vec<_>>().join("\n");
+        let (_, src_lines) = &ending.src_text;
+        let body_spans = ending.body_spans();
+        verifier
+            .verify(&body)
+            .into_iter()
+            .map(|TextDiagnostic { offset, message }| {
+                let position = map_offset(token, &body, offset, src_lines, body_spans)
+                    .unwrap_or(ending.header_span().end);
+                SourceDiagnostic { position, message }
+            })
+            .collect()
+    }
+
+    /// Verify the multi-line strings of all lines.
+    pub fn verify<Content>(&self, lines: &[TokenLine<Content>]) -> Vec<SourceDiagnostic>
+    where
+        Content: AsRef<str>,
+    {
+        lines
+            .iter()
+            .flat_map(|line| self.verify_line(line))
+            .collect()
+    }
+}
+
+/// Map an offset in the dedented body of a multi-line string back to the source text.
+fn map_offset<Content, Eol>(
+    token: &TextToken<Content>,
+    body: &str,
+    offset: usize,
+    src_lines: &[(Content, Eol)],
+    body_spans: &[Span],
+) -> Option<Position>
+where
+    Content: AsRef<str>,
+{
+    let offset = offset.min(body.len());
+    let index = body[..offset].matches('\n').count();
+    let line_start = body[..offset].rfind('\n').map_or(0, |eol| eol + 1);
+    let dedented = token.dedented_lines().nth(index)?;
+    let (src_line, _) = src_lines.get(index)?;
+    let src_line = src_line.as_ref();
+    let indent_len = src_line.len().checked_sub(dedented.len())?;
+    let column_len = indent_len + offset - line_start;
+    let span = body_spans.get(index)?;
+    let position = span.start.advance_inline(src_line.get(..column_len)?);
+    Some(position)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ast::LnCol, token::Scan};
+    use pretty_assertions::assert_eq;
+
+    fn verify(text: &str) -> Vec<(LnCol, String)> {
+        let lines: Vec<_> = Scan::new(text).collect();
+        TextVerifierRegistry::builtin()
+            .verify(&lines)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.position.ln_col, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn language() {
+        let text = "let a = '''yaml \n    abc: def\n  \n    ghi: jkl\nlet b = '''\n    abc\n";
+        let lines: Vec<_> = Scan::new(text).collect();
+        fn text_token<'a>(line: &TokenLine<&'a str>) -> TextToken<&'a str> {
+            match &line.ending.as_ref().unwrap().token {
+                EndingToken::Text(token) => token.clone(),
+                token => panic!("{token:?} is not a multi-line string"),
+            }
+        }
+        let first = text_token(&lines[0]);
+        assert_eq!(first.language(), Some("yaml"));
+        let dedented: Vec<_> = first.dedented_lines().collect();
+        assert_eq!(dedented, ["abc: def", "", "ghi: jkl"]);
+        assert_eq!(text_token(&lines[1]).language(), None);
+    }
+
+    #[test]
+    fn valid() {
+        let text = include_str!("../../tests/fixtures/multi-line.egg");
+        assert_eq!(verify(text), []);
+        assert_eq!(verify("let a = '''json\n    [1, 2, {\"a\": null}]\n"), []);
+        assert_eq!(verify("let a = '''\n    not: [valid\n"), []);
+        assert_eq!(verify("let a = '''rust\n    not: [valid\n"), []);
+    }
+
+    #[test]
+    fn position() {
+        macro_rules! case {
+            ($input:literal -> $line:literal:$column:literal $message:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                let position = LnCol::from_pred_counts($line - 1, $column - 1);
+                assert_eq!(verify($input), [(position, $message.to_string())]);
+            }};
+        }
+
+        case!("let a = '''json\n    {\"a\": 1,}\n" -> 2:13 "expected a string");
+        case!("let a = '''json\n    {\n      \"a\": tru\n    }\n" -> 3:12 "expected a value");
+        case!("let a = '''json\r\n    [\r\n\r\n        1 2]\r\n" -> 4:11 "expected ',' or ']'");
+        case!("  let a = '''yaml\n      a: 1\n        b: 2\n" -> 3:9 "unexpected indentation");
+        case!("let a = '''yaml\n    a: 1\n    a: 2\n" -> 3:5 "duplicate key \"a\"");
+        case!("let a = '''json\n" -> 1:16 "expected a value");
+    }
+
+    #[test]
+    fn custom_verifier() {
+        let mut registry = TextVerifierRegistry::new();
+        registry.register("upper", |body: &str| {
+            body.char_indices()
+                .filter(|(_, char)| char.is_lowercase())
+                .map(|(offset, char)| TextDiagnostic::new(offset, format!("{char:?} is lowercase")))
+                .collect()
+        });
+        let lines: Vec<_> = Scan::new("let a = '''upper\n    ABC\n    DeF\n").collect();
+        let received: Vec<_> = registry
+            .verify(&lines)
+            .into_iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect();
+        assert_eq!(received, ["3:6: 'e' is lowercase"]);
+    }
+}
@@ -0,0 +1,278 @@
+use super::TextDiagnostic;
+
+/// Maximum number of nested arrays and objects, deeper texts are reported instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Verify the syntax of a JSON text.
+///
+/// Verification stops at the first syntax error.
+pub fn verify_json(body: &str) -> Vec<TextDiagnostic> {
+    let mut parser = JsonParser {
+        text: body,
+        offset: 0,
+        depth: 0,
+    };
+    let result = parser.value().and_then(|()| {
+        parser.skip_whitespaces();
+        match parser.peek() {
+            None => Ok(()),
+            Some(_) => Err(parser.error("unexpected trailing characters")),
+        }
+    });
+    result.err().into_iter().collect()
+}
+
+/// Recursive descent parser that only checks the syntax of a JSON text.
+struct JsonParser<'a> {
+    text: &'a str,
+    offset: usize,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn error(&self, message: &str) -> TextDiagnostic {
+        TextDiagnostic::new(self.offset, message)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.offset += char.len_utf8();
+        Some(char)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let matched = self.peek() == Some(expected);
+        if matched {
+            self.offset += expected.len_utf8();
+        }
+        matched
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+        self.offset += rest.len() - trimmed.len();
+    }
+
+    fn value(&mut self) -> Result<(), TextDiagnostic> {
+        self.skip_whitespaces();
+        match self.peek() {
+            Some('{' | '[') if self.depth == MAX_DEPTH => Err(self.error("nesting is too deep")),
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => self.string(),
+            Some('-' | '0'..='9') => self.number(),
+            _ => self.literal(),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<(), TextDiagnostic>,
+    ) -> Result<(), TextDiagnostic> {
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn literal(&mut self) -> Result<(), TextDiagnostic> {
+        for literal in ["true", "false", "null"] {
+            if self.rest().starts_with(literal) {
+                self.offset += literal.len();
+                return Ok(());
+            }
+        }
+        Err(self.error("expected a value"))
+    }
+
+    fn object(&mut self) -> Result<(), TextDiagnostic> {
+        self.bump();
+        self.skip_whitespaces();
+        if self.eat('}') {
+            return Ok(());
+        }
+        loop {
+            self.skip_whitespaces();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a string"));
+            }
+            self.string()?;
+            self.skip_whitespaces();
+            if !self.eat(':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.value()?;
+            self.skip_whitespaces();
+            if self.eat('}') {
+                return Ok(());
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<(), TextDiagnostic> {
+        self.bump();
+        self.skip_whitespaces();
+        if self.eat(']') {
+            return Ok(());
+        }
+        loop {
+            self.value()?;
+            self.skip_whitespaces();
+            if self.eat(']') {
+                return Ok(());
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<(), TextDiagnostic> {
+        let start = self.offset;
+        self.bump();
+        loop {
+            let escape = self.offset;
+            match self.bump() {
+                None => return Err(TextDiagnostic::new(start, "unterminated string")),
+                Some('"') => return Ok(()),
+                Some('\\') => match self.bump() {
+                    Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => {}
+                    Some('u') => {
+                        let digits = self.rest().get(..4).unwrap_or("");
+                        if digits.len() != 4 || !digits.bytes().all(|byte| byte.is_ascii_hexdigit())
+                        {
+                            return Err(TextDiagnostic::new(escape, "invalid unicode escape"));
+                        }
+                        self.offset += 4;
+                    }
+                    _ => return Err(TextDiagnostic::new(escape, "invalid escape sequence")),
+                },
+                Some(char) if ('\u{0}'..='\u{1F}').contains(&char) => {
+                    return Err(TextDiagnostic::new(escape, "control character in string"));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<(), TextDiagnostic> {
+        let start = self.offset;
+        let invalid = || TextDiagnostic::new(start, "invalid number");
+        self.eat('-');
+        let digits = |parser: &mut Self| {
+            let rest = parser.rest();
+            let len = rest.len()
+                - rest
+                    .trim_start_matches(|char: char| char.is_ascii_digit())
+                    .len();
+            parser.offset += len;
+            len
+        };
+        match self.peek() {
+            Some('0') => {
+                self.bump();
+            }
+            Some('1'..='9') => {
+                digits(self);
+            }
+            _ => return Err(invalid()),
+        }
+        if self.eat('.') && digits(self) == 0 {
+            return Err(invalid());
+        }
+        if self.eat('e') || self.eat('E') {
+            let _ = self.eat('+') || self.eat('-');
+            if digits(self) == 0 {
+                return Err(invalid());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        macro_rules! case {
+            ($input:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(verify_json($input), []);
+            }};
+        }
+
+        case!("null");
+        case!(" true ");
+        case!("-12.5e+3");
+        case!("0");
+        case!(r#""abc \"def\" é \n""#);
+        case!("\"a\u{7F}b\u{9F}\"");
+        case!("[]");
+        case!("{}");
+        case!("[1, 2.5, \"three\", [true, false], {\"a\": null}]");
+        case!(
+            "{\n  \"name\": \"egg\",\n  \"tags\": [\"a\", \"b\"],\n  \"nested\": {\"x\": -0.5}\n}"
+        );
+    }
+
+    #[test]
+    fn negative() {
+        macro_rules! case {
+            ($input:literal -> $offset:literal $message:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(
+                    verify_json($input),
+                    [TextDiagnostic::new($offset, $message)]
+                );
+            }};
+        }
+
+        case!("" -> 0 "expected a value");
+        case!("nul" -> 0 "expected a value");
+        case!("[1, 2" -> 5 "expected ',' or ']'");
+        case!("[1, 2,]" -> 6 "expected a value");
+        case!("{\"a\" 1}" -> 5 "expected ':'");
+        case!("{\"a\": 1,}" -> 8 "expected a string");
+        case!("{a: 1}" -> 1 "expected a string");
+        case!("{\"a\": 1 \"b\": 2}" -> 8 "expected ',' or '}'");
+        case!("\"abc" -> 0 "unterminated string");
+        case!(r#""a\qb""# -> 2 "invalid escape sequence");
+        case!(r#""a\u12g4""# -> 2 "invalid unicode escape");
+        case!("\"a\tb\"" -> 2 "control character in string");
+        case!("01" -> 1 "unexpected trailing characters");
+        case!("1." -> 0 "invalid number");
+        case!("1e+" -> 0 "invalid number");
+        case!("-" -> 0 "invalid number");
+        case!("{} []" -> 3 "unexpected trailing characters");
+    }
+
+    #[test]
+    fn depth() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(verify_json(&nested(MAX_DEPTH)), []);
+        assert_eq!(
+            verify_json(&nested(MAX_DEPTH + 1)),
+            [TextDiagnostic::new(MAX_DEPTH, "nesting is too deep")],
+        );
+        let deep = "[{\"a\": ".repeat(100_000);
+        assert_eq!(
+            verify_json(&deep),
+            [TextDiagnostic::new(64 * 7, "nesting is too deep")],
+        );
+    }
+}
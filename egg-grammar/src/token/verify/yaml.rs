@@ -0,0 +1,328 @@
+use super::TextDiagnostic;
+use std::collections::HashSet;
+
+/// Verify the syntax of a subset of YAML.
+///
+/// **Supported subset:**
+/// * Block mappings (`key: value`) and block sequences (`- item`) that are nested by indentation.
+///   A block sequence must be indented deeper than the key that contains it.
+/// * Plain, single-quoted, and double-quoted scalars.
+/// * Single-line flow sequences (`[a, b]`) and flow mappings (`{a: b}`).
+/// * Literal (`|`) and folded (`>`) block scalars.
+/// * Comments (`# comment`) and document markers (`---`, `...`).
+///
+/// Verification stops at the first problem.
+pub fn verify_yaml(body: &str) -> Vec<TextDiagnostic> {
+    verify(body).err().into_iter().collect()
+}
+
+/// Kind of the entries of a [`Block`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Mapping,
+    Sequence,
+}
+
+/// Block of entries with the same indentation.
+#[derive(Debug)]
+struct Block<'a> {
+    indent: usize,
+    kind: Option<BlockKind>,
+    keys: HashSet<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    fn new(indent: usize) -> Self {
+        Block {
+            indent,
+            kind: None,
+            keys: HashSet::new(),
+        }
+    }
+
+    fn set_kind(&mut self, kind: BlockKind, offset: usize) -> Result<(), TextDiagnostic> {
+        match self.kind.replace(kind) {
+            Some(prev) if prev != kind => Err(TextDiagnostic::new(
+                offset,
+                "cannot mix mapping entries and sequence entries",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn verify(body: &str) -> Result<(), TextDiagnostic> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut expect_child = false;
+    let mut block_scalar_parent = None;
+    let mut line_start = 0;
+
+    for line in body.split('\n') {
+        let offset = line_start;
+        line_start += line.len() + 1;
+        let content = line.trim_start_matches(' ');
+        let indent = line.len() - content.len();
+
+        if let Some(parent) = block_scalar_parent {
+            if content.trim().is_empty() || indent > parent {
+                continue;
+            }
+            block_scalar_parent = None;
+        }
+
+        let content = strip_comment(content).trim_end();
+        if content.is_empty() {
+            continue;
+        }
+        if content.starts_with('\t') {
+            let message = "tabs are not allowed in indentation";
+            return Err(TextDiagnostic::new(offset + indent, message));
+        }
+        if indent == 0 && matches!(content, "---" | "...") {
+            blocks.clear();
+            expect_child = false;
+            continue;
+        }
+
+        match blocks.last() {
+            None => blocks.push(Block::new(indent)),
+            Some(top) if indent > top.indent => {
+                if !expect_child {
+                    return Err(TextDiagnostic::new(
+                        offset + indent,
+                        "unexpected indentation",
+                    ));
+                }
+                blocks.push(Block::new(indent));
+            }
+            Some(_) => {
+                while blocks.last().map_or(false, |block| block.indent > indent) {
+                    blocks.pop();
+                }
+                if blocks.last().map(|block| block.indent) != Some(indent) {
+                    let message = "indentation doesn't match any outer block";
+                    return Err(TextDiagnostic::new(offset + indent, message));
+                }
+            }
+        }
+        expect_child = false;
+
+        // an entry may start a nested block on the same line (`- key: value`, `- - item`)
+        let mut column = indent;
+        let mut entry = content;
+        let mut is_item = false;
+        loop {
+            let top = blocks.last_mut().expect("a block was pushed");
+
+            if entry == "-" || entry.starts_with("- ") {
+                top.set_kind(BlockKind::Sequence, offset + column)?;
+                let item = entry[1..].trim_start_matches(' ');
+                if item.is_empty() {
+                    expect_child = true;
+                    break;
+                }
+                column += entry.len() - item.len();
+                entry = item;
+                is_item = true;
+                blocks.push(Block::new(column));
+                continue;
+            }
+
+            let Some((key, value)) = split_key(entry) else {
+                verify_scalar(entry, offset + column)?;
+                if !is_item {
+                    let message = "expected a mapping entry or a sequence entry";
+                    return Err(TextDiagnostic::new(offset + column, message));
+                }
+                blocks.pop(); // a scalar item doesn't start a block
+                break;
+            };
+
+            top.set_kind(BlockKind::Mapping, offset + column)?;
+            verify_scalar(key, offset + column)?;
+            if !top.keys.insert(key) {
+                let message = format!("duplicate key {key:?}");
+                return Err(TextDiagnostic::new(offset + column, message));
+            }
+            let value = value.trim_start_matches(' ');
+            let value_column = column + entry.len() - value.len();
+            if value.is_empty() {
+                expect_child = true;
+            } else if is_block_scalar_header(value) {
+                block_scalar_parent = Some(indent);
+            } else {
+                verify_scalar(value, offset + value_column)?;
+            }
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the comment at the end of a line.
+fn strip_comment(line: &str) -> &'_ str {
+    let mut quote = None;
+    let mut prev = ' ';
+    let mut chars = line.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match (quote, char) {
+            (None, '#') if prev == ' ' => return &line[..index],
+            (None, '"' | '\'') if matches!(prev, ' ' | '[' | '{' | ',' | ':') => quote = Some(char),
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(current), _) if current == char => quote = None,
+            _ => {}
+        }
+        prev = char;
+    }
+    line
+}
+
+/// Split a mapping entry into its key and its value.
+fn split_key(entry: &str) -> Option<(&'_ str, &'_ str)> {
+    if entry.starts_with(['[', '{']) {
+        return None;
+    }
+    let key_end = match entry.chars().next() {
+        Some(quote @ ('"' | '\'')) => quoted_len(entry, quote)?,
+        _ => 0,
+    };
+    let colon = entry[key_end..]
+        .match_indices(':')
+        .map(|(index, _)| key_end + index)
+        .find(|&index| matches!(entry[(index + 1)..].chars().next(), None | Some(' ')))?;
+    Some((entry[..colon].trim_end(), &entry[(colon + 1)..]))
+}
+
+/// Get the length of the quoted scalar at the start of a text, including the quotes.
+fn quoted_len(text: &str, quote: char) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            '\'' if quote == '\'' && chars.peek().map(|(_, char)| *char) == Some('\'') => {
+                chars.next();
+            }
+            _ if char == quote => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Check if a value starts a block scalar (`|`, `>`, `|-`, `>+`, `|2`, etc).
+fn is_block_scalar_header(value: &str) -> bool {
+    let mut chars = value.chars();
+    matches!(chars.next(), Some('|' | '>'))
+        && chars.all(|char| matches!(char, '+' | '-' | '1'..='9'))
+}
+
+/// Verify a scalar or a flow collection.
+fn verify_scalar(value: &str, offset: usize) -> Result<(), TextDiagnostic> {
+    let len = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => quoted_len(value, quote)
+            .ok_or_else(|| TextDiagnostic::new(offset, "unterminated quoted scalar"))?,
+        Some('[' | '{') => flow_len(value, offset)?,
+        Some(char @ ('@' | '`')) => {
+            let message = format!("plain scalar must not start with {char:?}");
+            return Err(TextDiagnostic::new(offset, message));
+        }
+        _ => return Ok(()),
+    };
+    if !value[len..].trim().is_empty() {
+        let message = "unexpected characters after the end of the value";
+        return Err(TextDiagnostic::new(offset + len, message));
+    }
+    Ok(())
+}
+
+/// Get the length of the flow collection at the start of a text, including the brackets.
+fn flow_len(text: &str, offset: usize) -> Result<usize, TextDiagnostic> {
+    let mut stack = Vec::new();
+    let mut index = 0;
+    while let Some(char) = text[index..].chars().next() {
+        match char {
+            '[' => stack.push(']'),
+            '{' => stack.push('}'),
+            ']' | '}' => {
+                if stack.pop() != Some(char) {
+                    let message = format!("unexpected {char:?}");
+                    return Err(TextDiagnostic::new(offset + index, message));
+                }
+                if stack.is_empty() {
+                    return Ok(index + 1);
+                }
+            }
+            '"' | '\'' => {
+                index += quoted_len(&text[index..], char).ok_or_else(|| {
+                    TextDiagnostic::new(offset + index, "unterminated quoted scalar")
+                })?;
+                continue;
+            }
+            _ => {}
+        }
+        index += char.len_utf8();
+    }
+    Err(TextDiagnostic::new(offset, "unclosed flow collection"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positive() {
+        macro_rules! case {
+            ($input:expr) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(verify_yaml($input), []);
+            }};
+        }
+
+        case!("");
+        case!("title: This is some YAML\npages: 335\n");
+        case!("genres:\n    - drama\n    - tragedy\ncontributors:\n    author: Some guy\n");
+        case!("# comment\nabc: def # comment\n\n  # indented comment\nghi: 'jkl # not a comment'");
+        case!("- name: abc\n  tags: [a, 'b, c', {d: e}]\n- name: def\n");
+        case!("- - a\n  - b\n- c");
+        case!("\"quoted key\": \"value with \\\" quote\"\n'single': 'it''s'");
+        case!("text: |\n  line 1\n\n    line 2\nfolded: >-\n  abc\nnext: 1");
+        case!("---\na: 1\n---\na: 2\n...");
+        case!("url: http://example.com\ntime: 12:30");
+        case!("empty:\nnext: value");
+    }
+
+    #[test]
+    fn negative() {
+        macro_rules! case {
+            ($input:expr => $offset:literal $message:literal) => {{
+                eprintln!("TEST: {:?}", $input);
+                assert_eq!(
+                    verify_yaml($input),
+                    [TextDiagnostic::new($offset, $message)]
+                );
+            }};
+        }
+
+        case!("a: 1\n  b: 2" => 7 "unexpected indentation");
+        case!("a:\n    b: 1\n  c: 2" => 14 "indentation doesn't match any outer block");
+        case!("a:\n\tb: 1" => 3 "tabs are not allowed in indentation");
+        case!("a: 1\n- b" => 5 "cannot mix mapping entries and sequence entries");
+        case!("- a\nb: 1" => 4 "cannot mix mapping entries and sequence entries");
+        case!("a: 1\nb: 2\na: 3" => 10 "duplicate key \"a\"");
+        case!("a: 1\njust text" => 5 "expected a mapping entry or a sequence entry");
+        case!("a: \"abc" => 3 "unterminated quoted scalar");
+        case!("a: 'it's'" => 7 "unexpected characters after the end of the value");
+        case!("a: [1, 2" => 3 "unclosed flow collection");
+        case!("a: [1, 2}" => 8 "unexpected '}'");
+        case!("a: [1, 'b]" => 7 "unterminated quoted scalar");
+        case!("a: @b" => 3 "plain scalar must not start with '@'");
+        case!("- [a] b" => 5 "unexpected characters after the end of the value");
+        case!("\"a: 1" => 0 "unterminated quoted scalar");
+    }
+}
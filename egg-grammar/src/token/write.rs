@@ -0,0 +1,99 @@
+use super::{EolToken, TokenLine};
+use std::fmt::{self, Write};
+
+/// Get the string form of an optional EOL.
+fn eol_str(eol: Option<EolToken>) -> &'static str {
+    eol.map_or("", |eol| eol.as_str())
+}
+
+impl<Content> TokenLine<Content>
+where
+    Content: AsRef<str>,
+{
+    /// Write the source text of the line, the body lines of its ending token, and their EOLs.
+    ///
+    /// The text is assembled from the `src_text` of every item instead of [`TokenLine::ln_text`],
+    /// so modifications of the `src_text` of individual items are reflected in the output.
+    pub fn write_src_text(&self, out: &mut impl Write) -> fmt::Result {
+        out.write_str(self.indent.src_text.as_ref())?;
+        for item in &self.middle {
+            out.write_str(item.src_text.as_ref())?;
+        }
+        let Some(ending) = &self.ending else {
+            return out.write_str(eol_str(self.eol));
+        };
+        let (header, body) = &ending.src_text;
+        out.write_str(header.as_ref())?;
+        out.write_str(eol_str(self.eol))?;
+        for (ln_text, eol) in body {
+            out.write_str(ln_text.as_ref())?;
+            out.write_str(eol_str(*eol))?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the source text that was scanned into a list of [`TokenLine`].
+///
+/// The output is byte-identical to the scanned text unless the `src_text` of some items were modified.
+pub fn write_src_text<Content>(lines: &[TokenLine<Content>], out: &mut impl Write) -> fmt::Result
+where
+    Content: AsRef<str>,
+{
+    lines.iter().try_for_each(|line| line.write_src_text(out))
+}
+
+/// Reconstruct the source text that was scanned into a list of [`TokenLine`].
+///
+/// See [`write_src_text`].
+pub fn to_src_text<Content>(lines: &[TokenLine<Content>]) -> String
+where
+    Content: AsRef<str>,
+{
+    let mut text = String::new();
+    write_src_text(lines, &mut text).expect("writing to a String never fails");
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token::{MiddleToken, Scan, WordToken};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        macro_rules! case {
+            ($text:expr) => {{
+                let text = $text;
+                eprintln!("TEST: {text:?}");
+                let lines: Vec<_> = Scan::new(text).collect();
+                assert_eq!(to_src_text(&lines), text);
+            }};
+        }
+
+        case!("");
+        case!("\n");
+        case!("\n\n");
+        case!("abc");
+        case!("abc\n");
+        case!("abc\r\ndef\rghi\n");
+        case!("\tlet a = 1 # comment\n    let b = 2");
+        case!("let a = '''yaml\n    abc: 0\n\n    def: 1\r\n\nprint(a)");
+        case!("@@desc\r\n    Description\r\n  \r\nfn main() do\r\n");
+        case!("print 'hello world' with ❤️\0");
+    }
+
+    #[test]
+    fn modified_src_text() {
+        let text = "let abc = 123 # comment\nprint(abc)\n";
+        let mut lines: Vec<_> = Scan::new(text).collect();
+        for item in lines.iter_mut().flat_map(|line| &mut line.middle) {
+            if let Ok(MiddleToken::Word(WordToken::Identifier("abc"))) = item.token {
+                item.token = Ok(MiddleToken::Word(WordToken::Identifier("xyz")));
+                item.src_text = "xyz";
+            }
+        }
+        assert_eq!(to_src_text(&lines), "let xyz = 123 # comment\nprint(xyz)\n");
+    }
+}
@@ -1,4 +1,11 @@
-use egg_grammar::token::{Scan, TokenLine};
+use egg_grammar::{
+    ast::Span,
+    token::{
+        embed::doc::{DocKind, DocTokenTag},
+        to_src_text, EndingToken, EolToken, InvalidToken, MiddleToken, Scan, ShebangToken,
+        TokenLine,
+    },
+};
 use exec_diff::assert_eq_uni_diff;
 use itertools::Itertools;
 use pretty_assertions::assert_eq;
@@ -40,6 +47,58 @@ fn test_src_text(tokens: &[TokenLine<&str>]) {
     }
 }
 
+fn test_span(tokens: &[TokenLine<&str>], text: &str) {
+    title("Equality between src_text and the text covered by span");
+    let check = |span: Span, src_text: &str| {
+        dbg!(span, src_text);
+        assert_eq!(&text[span.start.offset..span.end.offset], src_text);
+        for position in [span.start, span.end] {
+            let preceding = &text[..position.offset];
+            let ln_pred = preceding.matches('\n').count();
+            let col_pred = preceding
+                .rsplit('\n')
+                .next()
+                .map_or(0, |ln| ln.chars().count());
+            assert_eq!(position.ln_col.line.pred_count(), ln_pred);
+            assert_eq!(position.ln_col.column.pred_count(), col_pred);
+        }
+    };
+    for line in tokens {
+        check(line.indent.span, line.indent.src_text);
+        for item in &line.middle {
+            check(item.span, item.src_text);
+        }
+        if let Some(ending) = &line.ending {
+            check(ending.header_span(), ending.src_text.0);
+            assert_eq!(ending.body_spans().len(), ending.body_ln_text().count());
+            for (span, body_line) in ending.body_spans().iter().zip(ending.body_ln_text()) {
+                check(*span, body_line);
+            }
+        }
+    }
+}
+
+fn test_eol(tokens: &[TokenLine<&str>], text: &str) {
+    title("Equality between the original source and all lines joined with their EOLs");
+    let received: String = tokens
+        .iter()
+        .flat_map(|line| line.all_ln_text().zip(line.all_eol()))
+        .map(|(ln_text, eol)| format!("{ln_text}{}", eol.map_or("", |eol| eol.as_str())))
+        .collect();
+    assert_eq!(received, text);
+    let unterminated = tokens
+        .iter()
+        .flat_map(TokenLine::all_eol)
+        .filter(Option::is_none)
+        .count();
+    assert!(unterminated <= 1);
+}
+
+fn test_write_src_text(tokens: &[TokenLine<&str>], text: &str) {
+    title("Equality between the original source and the reconstructed source");
+    assert_eq!(to_src_text(tokens), text);
+}
+
 #[test]
 fn hello_world() {
     let text = include_str!("fixtures/hello-world.egg");
@@ -47,6 +106,9 @@ fn hello_world() {
     test_snapshot!(tokens, "snapshots/token-scan/hello-world.txt");
     test_ln_text(&tokens, text);
     test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
 }
 
 #[test]
@@ -56,6 +118,9 @@ fn multi_line() {
     test_snapshot!(tokens, "snapshots/token-scan/multi-line.txt");
     test_ln_text(&tokens, text);
     test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
 }
 
 #[test]
@@ -65,6 +130,9 @@ fn invalid_char_heart() {
     test_snapshot!(tokens, "snapshots/token-scan/invalid-char-heart.txt");
     test_ln_text(&tokens, text);
     test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
 }
 
 #[test]
@@ -74,12 +142,200 @@ fn invalid_char_nul() {
     test_snapshot!(tokens, "snapshots/token-scan/invalid-char-nul.txt");
     test_ln_text(&tokens, text);
     test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
 }
 
 #[test]
 fn example_fibonacci() {
     let text = include_str!("fixtures/examples/fibonacci.egg");
     let tokens: Vec<_> = dbg!(Scan::new(text).collect());
+    let shebang = tokens[0].ending.as_ref().map(|item| &item.token);
+    assert_eq!(
+        shebang,
+        Some(&EndingToken::Shebang(ShebangToken(" /usr/bin/env egg")))
+    );
+    let Some(EndingToken::Doc(module_doc)) = tokens[1].ending.as_ref().map(|item| &item.token)
+    else {
+        panic!("second line should be a documentation");
+    };
+    assert_eq!(module_doc.header.0, DocTokenTag::inner(Some("desc".into())));
     test_ln_text(&tokens, text);
     test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
+}
+
+#[test]
+fn trailing_doc() {
+    /// Kind, name, attribute and number of body lines of a documentation.
+    type DocSummary<'a> = (DocKind, Option<String>, &'a str, usize);
+
+    /// Summarize every line as its middle tokens and its documentation.
+    fn summarize(text: &str) -> Vec<(String, Option<DocSummary<'_>>)> {
+        let tokens: Vec<_> = dbg!(Scan::new(text).collect());
+        test_ln_text(&tokens, text);
+        test_src_text(&tokens);
+        test_span(&tokens, text);
+        test_eol(&tokens, text);
+        test_write_src_text(&tokens, text);
+        tokens
+            .iter()
+            .map(|line| {
+                let middle: String = line.middle.iter().map(|item| item.src_text).collect();
+                let doc = line.ending.as_ref().map(|item| match &item.token {
+                    EndingToken::Doc(doc) => {
+                        let (tag, attr) = &doc.header;
+                        let name = tag.name.as_ref().map(|name| name.as_str().to_string());
+                        (tag.kind, name, attr.0, doc.body.len())
+                    }
+                    token => panic!("{token:?} is not a documentation"),
+                });
+                (middle, doc)
+            })
+            .collect()
+    }
+
+    title("Trailing documentations have no body");
+    let text = "pub enum Result(X, E) with\n    pub case Ok(X) @@desc Contain the success value.\n    pub case Err(E) +@@desc Contain\n        the failure value.\n";
+    let expected = [
+        ("pub enum Result(X, E) with".to_string(), None),
+        (
+            "pub case Ok(X) ".to_string(),
+            Some((
+                DocKind::Trailing,
+                Some("desc".to_string()),
+                " Contain the success value.",
+                0,
+            )),
+        ),
+        (
+            "pub case Err(E) +".to_string(),
+            Some((DocKind::Trailing, Some("desc".to_string()), " Contain", 0)),
+        ),
+        ("the failure value.".to_string(), None),
+    ];
+    assert_eq!(summarize(text), expected);
+
+    title("Lines after a trailing documentation on a block-opening line are code");
+    let text =
+        "pub enum Result(X, E) with @@desc Result type\n    pub case Ok(X)\n    pub case Err(E)\n";
+    let expected = [
+        (
+            "pub enum Result(X, E) with ".to_string(),
+            Some((
+                DocKind::Trailing,
+                Some("desc".to_string()),
+                " Result type",
+                0,
+            )),
+        ),
+        ("pub case Ok(X)".to_string(), None),
+        ("pub case Err(E)".to_string(), None),
+    ];
+    assert_eq!(summarize(text), expected);
+
+    title("Leading documentations keep their body");
+    let text = "@@desc Result\n    type\npub enum Result(X, E) with\n";
+    let expected = [
+        (
+            "".to_string(),
+            Some((DocKind::Outer, Some("desc".to_string()), " Result", 1)),
+        ),
+        ("pub enum Result(X, E) with".to_string(), None),
+    ];
+    assert_eq!(summarize(text), expected);
+}
+
+#[test]
+fn eol_crlf() {
+    let text = "@@desc Text with CRLF.\r\npub const TEXT: str = '''\r\n    abc\r\n\r\n    def\r\nprint(TEXT)";
+    let tokens: Vec<_> = dbg!(Scan::new(text).collect());
+    test_ln_text(&tokens, text);
+    test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
+    title("EOL of every line");
+    let received: Vec<_> = tokens.iter().flat_map(TokenLine::all_eol).collect();
+    use EolToken::Crlf;
+    let expected = [
+        Some(Crlf),
+        Some(Crlf),
+        Some(Crlf),
+        Some(Crlf),
+        Some(Crlf),
+        None,
+    ];
+    assert_eq!(received, expected);
+    assert_eq!(tokens.iter().flat_map(TokenLine::invalid_eol).count(), 0);
+}
+
+#[test]
+fn eol_lone_cr() {
+    let text = "print 'abc\rdef'\r\n# comment\rprint(0)\n";
+    let tokens: Vec<_> = dbg!(Scan::new(text).collect());
+    test_src_text(&tokens);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
+    title("Lone CR ends the line and is reported as invalid");
+    let received: Vec<_> = tokens.iter().map(|line| (line.ln_text, line.eol)).collect();
+    use EolToken::{Cr, Crlf, Lf};
+    let expected = [
+        ("print 'abc", Some(Cr)),
+        ("def'", Some(Crlf)),
+        ("# comment", Some(Cr)),
+        ("print(0)", Some(Lf)),
+    ];
+    assert_eq!(received, expected);
+    let invalid: Vec<_> = tokens.iter().flat_map(TokenLine::invalid_eol).collect();
+    assert_eq!(invalid, [Cr, Cr]);
+    let ln_num: Vec<_> = tokens
+        .iter()
+        .map(|line| line.indent.span.start.ln_col.line.value().get())
+        .collect();
+    assert_eq!(ln_num, [1, 2, 3, 4]);
+}
+
+#[test]
+fn invalid_char_run() {
+    let text = "let ❤️❤️ = “abc” ∈ x e\u{301} abe\u{301} 'abc'\u{301} (\u{301}# ❤️ in comment";
+    let tokens: Vec<_> = dbg!(Scan::new(text).collect());
+    test_ln_text(&tokens, text);
+    test_src_text(&tokens);
+    test_span(&tokens, text);
+    test_eol(&tokens, text);
+    test_write_src_text(&tokens, text);
+    title("Consecutive invalid characters are grouped into whole grapheme clusters");
+    let received: Vec<_> = tokens[0]
+        .middle
+        .iter()
+        .filter_map(|item| match &item.token {
+            Ok(_) => None,
+            Err(InvalidToken(chars)) => Some((item.src_text, chars.clone())),
+        })
+        .collect();
+    let expected = [
+        ("❤\u{fe0f}❤\u{fe0f}", vec!['❤', '\u{fe0f}', '❤', '\u{fe0f}']),
+        ("“", vec!['“']),
+        ("”", vec!['”']),
+        ("∈", vec!['∈']),
+        ("e\u{301}", vec!['e', '\u{301}']),
+        ("e\u{301}", vec!['e', '\u{301}']),
+        ("\u{301}", vec!['\u{301}']),
+        ("\u{301}", vec!['\u{301}']),
+    ];
+    assert_eq!(received, expected);
+    title("Tokens other than words are kept whole before a combining mark");
+    let strings: Vec<_> = tokens[0]
+        .middle
+        .iter()
+        .filter_map(|item| match &item.token {
+            Ok(MiddleToken::String(token)) => Some((item.src_text, token.error)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(strings, [("'abc'", None)]);
 }